@@ -0,0 +1,172 @@
+use crate::ai::{handiterators::*, *};
+use crate::game::*;
+
+// Soft evidence about hidden hands: Relative likelihoods (in (0,1]) of observed decisions given the hands.
+// Sampled hands are accepted with this likelihood (rejection sampling), so that simulation
+// does not assume uniformly distributed opponent hands.
+
+fn count_trumpf(rules: &dyn TRules, slccard: &[SCard]) -> usize {
+    slccard.iter().filter(|card| rules.trumpforfarbe(**card).is_trumpf()).count()
+}
+
+// Players announcing a game or giving Stoss usually hold more than their fair share of trumpf.
+fn likelihood_strong_trumpf(rules: &dyn TRules, hand: SFullHand, f_fair_share_factor: f64) -> f64 {
+    let slccard = hand.get();
+    let ekurzlang = EKurzLang::from_cards_per_player(slccard.len());
+    let n_trumpf_total = SCard::values(ekurzlang)
+        .filter(|card| rules.trumpforfarbe(*card).is_trumpf())
+        .count();
+    let f_trumpf_expected = n_trumpf_total.as_num::<f64>() / EPlayerIndex::SIZE.as_num::<f64>() * f_fair_share_factor;
+    0.5f64.powf((f_trumpf_expected - count_trumpf(rules, slccard).as_num::<f64>()).max(0.))
+}
+
+// Players not announcing a Solo usually do not hold many Ober, Unter and cards of a single farbe.
+fn likelihood_no_solo(hand: SFullHand) -> f64 {
+    let slccard = hand.get();
+    let n_trumpf_solo_max = unwrap!(EFarbe::values()
+        .map(|efarbe| slccard.iter()
+            .filter(|card| matches!(card.schlag(), ESchlag::Ober|ESchlag::Unter) || card.farbe()==efarbe)
+            .count()
+        )
+        .max());
+    let n_trumpf_solo_threshold = slccard.len() * 3 / 4;
+    0.25f64.powi((n_trumpf_solo_max + 1).saturating_sub(n_trumpf_solo_threshold).as_num::<i32>())
+}
+
+fn likelihood_announcement_decision(rules: &dyn TRules, eannouncementdecision: EAnnouncementDecision, hand: SFullHand) -> f64 {
+    match eannouncementdecision {
+        EAnnouncementDecision::AnnouncedRufspiel => likelihood_strong_trumpf(rules, hand, /*f_fair_share_factor*/1.2),
+        EAnnouncementDecision::AnnouncedSolo => likelihood_strong_trumpf(rules, hand, /*f_fair_share_factor*/1.5),
+        EAnnouncementDecision::Passed => likelihood_no_solo(hand),
+    }
+}
+
+// Players leading a farbe usually start with its Ass (if they hold it).
+fn likelihood_card_play(rules: &dyn TRules, stichseq: &SStichSequence, ahand_full: &EnumMap<EPlayerIndex, SHand>, epi_fixed: EPlayerIndex) -> f64 {
+    let mut ahand_simulate = ahand_full.clone();
+    let mut f_likelihood = 1.;
+    for stich in stichseq.visible_stichs().iter().filter(|stich| !stich.is_empty()) {
+        let epi_first = stich.first_playerindex();
+        let card_first = *stich.first();
+        if epi_first!=epi_fixed && card_first.schlag()!=ESchlag::Ass {
+            if let VTrumpfOrFarbe::Farbe(efarbe) = rules.trumpforfarbe(card_first) {
+                let card_ass = SCard::new(efarbe, ESchlag::Ass);
+                if rules.trumpforfarbe(card_ass)==VTrumpfOrFarbe::Farbe(efarbe) && ahand_simulate[epi_first].contains(card_ass) {
+                    f_likelihood *= 0.5;
+                }
+            }
+        }
+        for (epi, card) in stich.iter() {
+            ahand_simulate[epi].play_card(*card);
+        }
+    }
+    f_likelihood
+}
+
+pub fn hand_likelihood(game: &SGame, epi_fixed: EPlayerIndex, ahand: &EnumMap<EPlayerIndex, SHand>) -> f64 {
+    let rules = game.rules.as_ref();
+    let ahand_full = ahand_at_game_start(&game.stichseq, ahand);
+    EPlayerIndex::values()
+        .filter(|epi| *epi!=epi_fixed)
+        .map(|epi| {
+            let hand = SFullHand::new(ahand_full[epi].cards(), game.kurzlang());
+            rules.announcement_decision(epi)
+                .map_or(1., |eannouncementdecision| likelihood_announcement_decision(rules, eannouncementdecision, hand))
+                * if game.vecstoss.iter().any(|stoss| stoss.epi==epi) {
+                    likelihood_strong_trumpf(rules, hand, /*f_fair_share_factor*/1.5)
+                } else {
+                    1.
+                }
+        })
+        .product::<f64>()
+        * likelihood_card_play(rules, &game.stichseq, &ahand_full, epi_fixed)
+}
+
+// like forever_rand_hands, but hands are distributed according to hand_likelihood
+pub fn forever_rand_hands_inferred(game: &SGame, epi_fixed: EPlayerIndex) -> impl Iterator<Item=EnumMap<EPlayerIndex, SHand>> + '_ {
+    const N_ATTEMPTS_MAX: usize = 100; // unlikely evidence must not stall sampling
+    let mut itahand = forever_rand_hands(&game.stichseq, game.ahand[epi_fixed].clone(), epi_fixed, game.open_hand(), game.rules.as_ref());
+    std::iter::from_fn(move || {
+        let mut oahand_last = None;
+        for ahand in itahand.by_ref().take(N_ATTEMPTS_MAX) {
+            if rand::thread_rng().gen::<f64>() < hand_likelihood(game, epi_fixed, &ahand) {
+                return Some(ahand);
+            }
+            oahand_last = Some(ahand);
+        }
+        oahand_last // all attempts rejected: fall back to uniform sampling
+    })
+}
+
+// like all_possible_hands, but hands are kept according to hand_likelihood
+pub fn all_possible_hands_inferred(game: &SGame, epi_fixed: EPlayerIndex) -> Vec<EnumMap<EPlayerIndex, SHand>> {
+    let vecahand = all_possible_hands(&game.stichseq, game.ahand[epi_fixed].clone(), epi_fixed, game.open_hand(), game.rules.as_ref())
+        .collect::<Vec<_>>();
+    let vecahand_inferred = vecahand.iter()
+        .filter(|ahand| rand::thread_rng().gen::<f64>() < hand_likelihood(game, epi_fixed, ahand))
+        .cloned()
+        .collect::<Vec<_>>();
+    if vecahand_inferred.is_empty() {
+        vecahand // all hands rejected by chance
+    } else {
+        vecahand_inferred
+    }
+}
+
+#[test]
+#[cfg(not(feature="dreier"))]
+fn test_likelihood_no_solo() {
+    use crate::card::card_values::*;
+    let likelihood = |acard: [SCard; 8]| likelihood_no_solo(SFullHand::new(&acard, EKurzLang::Lang));
+    assert_eq!(likelihood([E7, E8, G7, G8, H7, H8, S7, S8]), 1.);
+    assert!(likelihood([EO, GO, HO, EU, GU, HZ, HA, H9]) < likelihood([EO, GO, HO, EU, GU, EA, SA, GA]));
+    assert!(likelihood([EO, GO, HO, SO, EU, GU, HU, SU]) < likelihood([EO, GO, HO, EU, GU, HZ, SA, G9]));
+}
+
+#[test]
+#[cfg(not(feature="dreier"))]
+fn test_forever_rand_hands_inferred() {
+    use crate::card::card_values::*;
+    use crate::rules::{payoutdecider::*, rulesrufspiel::*};
+    let rules = SRulesRufspiel::new(EPlayerIndex::EPI1, EFarbe::Eichel, SPayoutDeciderParams::new(
+        /*n_payout_base*/10,
+        /*n_payout_schneider_schwarz*/10,
+        SLaufendeParams::new(/*n_payout_per_lauf*/10, /*n_lauf_lbound*/3),
+    ));
+    let mut game = SGame::new(
+        EPlayerIndex::map_from_raw([
+            [E7, E8, G7, G8, H7, H8, S7, S8],
+            [EO, GO, HO, SO, EU, GU, E9, HA],
+            [HU, SU, EA, EZ, EK, GA, GZ, GK],
+            [G9, HZ, HK, H9, SA, SZ, SK, S9],
+        ]).map_into(|acard| acard.into()),
+        SDoublings::new(SStaticEPI0{}),
+        /*ostossparams*/None,
+        Box::new(rules.clone()),
+        /*n_stock*/0,
+    );
+    let n_samples = 1000;
+    let avg_trumpf_declarer = |itahand: &mut dyn Iterator<Item=EnumMap<EPlayerIndex, SHand>>| {
+        itahand.take(n_samples)
+            .map(|ahand| count_trumpf(&rules, ahand[EPlayerIndex::EPI1].cards()))
+            .sum::<usize>().as_num::<f64>() / n_samples.as_num::<f64>()
+    };
+    assert!(
+        avg_trumpf_declarer(&mut forever_rand_hands(&game.stichseq, game.ahand[EPlayerIndex::EPI0].clone(), EPlayerIndex::EPI0, None, &rules))
+        < avg_trumpf_declarer(&mut forever_rand_hands_inferred(&game, EPlayerIndex::EPI0))
+    );
+    // exhaustive enumeration in the endgame is thinned out the same way
+    for card in [S7, SO, GA, SA, EO, HU, HZ, H7, GO, SU, HK, H8, HO, GZ, H9, G8] {
+        let epi = unwrap!(game.which_player_can_do_something()).0;
+        unwrap!(game.zugeben(card, epi));
+    }
+    let avg_likelihood = |slcahand: &[EnumMap<EPlayerIndex, SHand>]| {
+        slcahand.iter()
+            .map(|ahand| hand_likelihood(&game, EPlayerIndex::EPI0, ahand))
+            .sum::<f64>() / slcahand.len().as_num::<f64>()
+    };
+    assert!(
+        avg_likelihood(&all_possible_hands(&game.stichseq, game.ahand[EPlayerIndex::EPI0].clone(), EPlayerIndex::EPI0, None, &rules).collect::<Vec<_>>())
+        < avg_likelihood(&(0..20).flat_map(|_| all_possible_hands_inferred(&game, EPlayerIndex::EPI0)).collect::<Vec<_>>())
+    );
+}
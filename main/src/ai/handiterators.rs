@@ -33,6 +33,7 @@ pub struct SHandIterator<NextVecEPI> {
     veccard_unknown: Vec<SCard>,
     vecepi: Vec<EPlayerIndex>,
     hand_known: SHand,
+    otplepihand_open: Option<(EPlayerIndex, SHand)>,
     b_valid: bool,
     phantom: std::marker::PhantomData<NextVecEPI>,
 }
@@ -43,6 +44,9 @@ impl<NextVecEPI: TNextVecEPI> Iterator for SHandIterator<NextVecEPI> {
         if_then_some!(self.b_valid, {
             let mut ahand = EPlayerIndex::map_from_fn(|_| SHand::new_from_vec(SHandVector::new()));
             ahand[self.epi_fixed] = self.hand_known.clone();
+            if let Some((epi_open, hand_open)) = self.otplepihand_open.as_ref() {
+                ahand[*epi_open] = hand_open.clone();
+            }
             for (i, epi) in self.vecepi.iter().copied().enumerate() {
                 ahand[epi].add_card(self.veccard_unknown[i]);
             }
@@ -52,13 +56,18 @@ impl<NextVecEPI: TNextVecEPI> Iterator for SHandIterator<NextVecEPI> {
     }
 }
 
-fn make_handiterator<NextVecEPI: TNextVecEPI>(stichseq: &SStichSequence, hand_fixed: SHand, epi_fixed: EPlayerIndex) -> SHandIterator<NextVecEPI> {
-    let veccard_unknown = unplayed_cards(stichseq, &hand_fixed).collect::<Vec<_>>();
+fn make_handiterator<NextVecEPI: TNextVecEPI>(stichseq: &SStichSequence, hand_fixed: SHand, epi_fixed: EPlayerIndex, otplepihand_open: Option<(EPlayerIndex, SHand)>) -> SHandIterator<NextVecEPI> {
+    let otplepihand_open = otplepihand_open.filter(|(epi_open, _hand_open)| *epi_open!=epi_fixed);
+    let veccard_unknown = unplayed_cards(stichseq, &hand_fixed)
+        .filter(|card| otplepihand_open.as_ref().map_or(true, |(_epi_open, hand_open)| !hand_open.contains(*card)))
+        .collect::<Vec<_>>();
     let mapepin_cards_per_hand = remaining_cards_per_hand(stichseq);
     let mut vecepi = Vec::new();
     for epi in EPlayerIndex::values() {
         if epi==epi_fixed {
             assert_eq!(mapepin_cards_per_hand[epi], hand_fixed.cards().len());
+        } else if let Some((_epi_open, hand_open)) = otplepihand_open.as_ref().filter(|(epi_open, _hand_open)| *epi_open==epi) {
+            assert_eq!(mapepin_cards_per_hand[epi], hand_open.cards().len());
         } else {
             vecepi.extend(std::iter::repeat(epi).take(mapepin_cards_per_hand[epi]));
         }
@@ -71,25 +80,30 @@ fn make_handiterator<NextVecEPI: TNextVecEPI>(stichseq: &SStichSequence, hand_fi
         veccard_unknown,
         vecepi,
         hand_known: hand_fixed,
+        otplepihand_open,
         b_valid: true, // in the beginning, there should be a valid assignment of cards to players
         phantom: std::marker::PhantomData,
     }
 }
 
-fn make_handiterator_compatible_with_game_so_far<'lifetime, NextVecEPI: TNextVecEPI+'lifetime>(stichseq: &'lifetime SStichSequence, hand_fixed: SHand, epi_fixed: EPlayerIndex, rules: &'lifetime dyn TRules) -> impl Iterator<Item=EnumMap<EPlayerIndex, SHand>> + 'lifetime {
-    make_handiterator::<NextVecEPI>(stichseq, hand_fixed, epi_fixed)
+pub fn ahand_at_game_start(stichseq: &SStichSequence, ahand: &EnumMap<EPlayerIndex, SHand>) -> EnumMap<EPlayerIndex, SHand> {
+    EPlayerIndex::map_from_fn(|epi| {
+        let mut veccard = ahand[epi].cards().clone();
+        veccard.extend(stichseq.current_stich().get(epi).copied().into_iter());
+        veccard.extend(stichseq.completed_stichs().iter().rev().map(|stich| stich[epi]));
+        assert_eq!(veccard.len(), stichseq.kurzlang().cards_per_player());
+        SHand::new_from_vec(veccard)
+    })
+}
+
+fn make_handiterator_compatible_with_game_so_far<'lifetime, NextVecEPI: TNextVecEPI+'lifetime>(stichseq: &'lifetime SStichSequence, hand_fixed: SHand, epi_fixed: EPlayerIndex, otplepihand_open: Option<(EPlayerIndex, SHand)>, rules: &'lifetime dyn TRules) -> impl Iterator<Item=EnumMap<EPlayerIndex, SHand>> + 'lifetime {
+    make_handiterator::<NextVecEPI>(stichseq, hand_fixed, epi_fixed, otplepihand_open)
         .filter(move |ahand| {
             let stich_current = stichseq.current_stich();
             assert!(!stich_current.is_full());
             assert!(ahand_vecstich_card_count_is_compatible(stichseq, ahand));
             // hands must not contain other cards preventing farbe/trumpf frei
-            let mut ahand_simulate = EPlayerIndex::map_from_fn(|epi| {
-                let mut veccard = ahand[epi].cards().clone();
-                veccard.extend(stich_current.get(epi).copied().into_iter());
-                veccard.extend(stichseq.completed_stichs().iter().rev().map(|stich| stich[epi]));
-                assert_eq!(veccard.len(), stichseq.kurzlang().cards_per_player());
-                SHand::new_from_vec(veccard)
-            });
+            let mut ahand_simulate = ahand_at_game_start(stichseq, ahand);
             rules.playerindex().map_or(true, |epi_active|
                 rules.can_be_played(SFullHand::new(ahand_simulate[epi_active].cards(), stichseq.kurzlang()))
             )
@@ -117,15 +131,17 @@ fn make_handiterator_compatible_with_game_so_far<'lifetime, NextVecEPI: TNextVec
         })
 }
 
-pub fn all_possible_hands<'lifetime>(stichseq: &'lifetime SStichSequence, hand_fixed: SHand, epi_fixed: EPlayerIndex, rules: &'lifetime dyn TRules) -> impl Iterator<Item=EnumMap<EPlayerIndex, SHand>> + 'lifetime {
-    make_handiterator_compatible_with_game_so_far::<SNextVecEPIPermutation>(stichseq, hand_fixed, epi_fixed, rules)
+// otplepihand_open: hand visible to everybody (see TRules::playerindex_open_hand)
+pub fn all_possible_hands<'lifetime>(stichseq: &'lifetime SStichSequence, hand_fixed: SHand, epi_fixed: EPlayerIndex, otplepihand_open: Option<(EPlayerIndex, SHand)>, rules: &'lifetime dyn TRules) -> impl Iterator<Item=EnumMap<EPlayerIndex, SHand>> + 'lifetime {
+    make_handiterator_compatible_with_game_so_far::<SNextVecEPIPermutation>(stichseq, hand_fixed, epi_fixed, otplepihand_open, rules)
 }
 
-pub fn forever_rand_hands<'lifetime>(stichseq: &'lifetime SStichSequence, hand_fixed: SHand, epi_fixed: EPlayerIndex, rules: &'lifetime dyn TRules) -> impl Iterator<Item=EnumMap<EPlayerIndex, SHand>> + 'lifetime {
-    make_handiterator_compatible_with_game_so_far::<SNextVecEPIShuffle>(stichseq, hand_fixed, epi_fixed, rules)
+pub fn forever_rand_hands<'lifetime>(stichseq: &'lifetime SStichSequence, hand_fixed: SHand, epi_fixed: EPlayerIndex, otplepihand_open: Option<(EPlayerIndex, SHand)>, rules: &'lifetime dyn TRules) -> impl Iterator<Item=EnumMap<EPlayerIndex, SHand>> + 'lifetime {
+    make_handiterator_compatible_with_game_so_far::<SNextVecEPIShuffle>(stichseq, hand_fixed, epi_fixed, otplepihand_open, rules)
 }
 
 #[test]
+#[cfg(not(feature="dreier"))]
 fn test_all_possible_hands() {
     use crate::card::card_values::*;
     let epi_irrelevant = EPlayerIndex::EPI0;
@@ -169,6 +185,7 @@ fn test_all_possible_hands() {
                     &stichseq,
                     SHand::new_from_iter(veccard_hand),
                     epi_fixed,
+                    /*otplepihand_open*/None,
                 )
                     .inspect(|ahand| assert_eq!(EnumMap::from_raw(an_size_hand), ahand.map(|hand| hand.cards().len())))
                     .count(),
@@ -179,3 +196,57 @@ fn test_all_possible_hands() {
     }
 }
 
+#[test]
+#[cfg(feature="dreier")]
+fn test_all_possible_hands_dreier() {
+    use crate::card::card_values::*;
+    let epi_irrelevant = EPlayerIndex::EPI0;
+    let mut stichseq = SStichSequence::new(EKurzLang::Kurz);
+    for acard_stich in [[E9, EZ, EA], [G9, GZ, GA]] {
+        for card in acard_stich {
+            stichseq.zugeben_custom_winner_index(card, |_stich| epi_irrelevant);
+        }
+    }
+    let epi_fixed = EPlayerIndex::EPI2;
+    for (card, veccard_hand, n_hand_count, an_size_hand) in [
+        (H9, vec![EO, GO, HO, SO, EU, GU], 924, [6, 6, 6]), // binomial(12, 6)
+        (HZ, vec![EO, GO, HO, SO, EU, GU], 462, [5, 6, 6]),
+        (EO, vec![EO, GO, HO, SO, EU, GU], 252, [5, 5, 6]),
+        (HU, vec![GO, HO, SO, EU, GU], 252, [5, 5, 5]),
+    ] {
+        assert_eq!(
+            make_handiterator::<SNextVecEPIPermutation>(
+                &stichseq,
+                SHand::new_from_iter(veccard_hand),
+                epi_fixed,
+                /*otplepihand_open*/None,
+            )
+                .inspect(|ahand| assert_eq!(EnumMap::from_raw(an_size_hand), ahand.map(|hand| hand.cards().len())))
+                .count(),
+            n_hand_count
+        );
+        stichseq.zugeben_custom_winner_index(card, |_stich| epi_irrelevant);
+    }
+}
+
+#[test]
+#[cfg(not(feature="dreier"))]
+fn test_all_possible_hands_open_hand() {
+    use crate::card::card_values::*;
+    let mut stichseq = SStichSequence::new(EKurzLang::Lang);
+    for card in [G7, G8, GA, G9] {
+        stichseq.zugeben_custom_winner_index(card, |_stich| EPlayerIndex::EPI0);
+    }
+    let hand_open = SHand::new_from_iter([EO, GO, HO, SO, EU, GU, HU]);
+    assert_eq!(
+        make_handiterator::<SNextVecEPIPermutation>(
+            &stichseq,
+            SHand::new_from_iter([SU, EA, EZ, EK, E9, E8, E7]),
+            EPlayerIndex::EPI0,
+            Some((EPlayerIndex::EPI3, hand_open.clone())),
+        )
+            .inspect(|ahand| assert_eq!(ahand[EPlayerIndex::EPI3].cards(), hand_open.cards()))
+            .count(),
+        3432, // binomial(14, 7)
+    );
+}
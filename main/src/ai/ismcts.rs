@@ -0,0 +1,118 @@
+use crate::ai::*;
+use std::time::{Duration, Instant};
+
+// Single-observer information set Monte Carlo tree search (SO-ISMCTS):
+// Each iteration samples hands compatible with what epi_fixed knows and descends the (shared) tree
+// only along cards allowed in this sample. Thus, nodes correspond to information sets of epi_fixed
+// and - in contrast to sampled minimax - no player can exploit knowledge of hidden cards.
+
+struct SIsmctsNode {
+    epi: EPlayerIndex, // player who played the card leading to this node
+    vectplcardi_child: Vec<(SCard, usize)>, // indices into vecnode
+    n_visits: usize,
+    n_availability: usize, // how often this node could have been chosen by its parent
+    n_payout_sum: isize, // payout of epi, summed over all visits
+}
+
+impl SIsmctsNode {
+    fn new(epi: EPlayerIndex) -> Self {
+        Self {
+            epi,
+            vectplcardi_child: Vec::new(),
+            n_visits: 0,
+            n_availability: 0,
+            n_payout_sum: 0,
+        }
+    }
+
+    fn child(&self, card: SCard) -> Option<usize> {
+        self.vectplcardi_child.iter()
+            .find(|&&(card_child, _i_node)| card_child==card)
+            .map(|&(_card, i_node)| i_node)
+    }
+}
+
+pub fn ismcts(
+    determinebestcard: &SDetermineBestCard,
+    itahand: impl Iterator<Item=EnumMap<EPlayerIndex, SHand>>,
+    tpln_stoss_doubling: (usize, usize),
+    n_stock: isize,
+    duration: Duration,
+) -> SCard {
+    let rules = determinebestcard.rules;
+    let instant_end = Instant::now() + duration;
+    let mut rng = rand::thread_rng();
+    let mut vecnode = vec![SIsmctsNode::new(/*epi irrelevant for root*/determinebestcard.epi_fixed)];
+    let mut n_payout_abs_max = 1; // scales exploration to the magnitude of payouts
+    for mut ahand in itahand {
+        let mut stichseq = determinebestcard.stichseq.clone();
+        let mut veci_node = Vec::new();
+        let mut i_node = 0;
+        let play_card = |ahand: &mut EnumMap<EPlayerIndex, SHand>, stichseq: &mut SStichSequence, epi, card| {
+            ahand[epi].play_card(card);
+            stichseq.zugeben(card, rules);
+        };
+        // selection and expansion
+        while !stichseq.game_finished() {
+            let epi = unwrap!(stichseq.current_stich().current_playerindex());
+            let veccard_allowed = rules.all_allowed_cards(&stichseq, &ahand[epi]);
+            let veccard_unexpanded = veccard_allowed.iter().copied()
+                .filter(|card| vecnode[i_node].child(*card).is_none())
+                .collect::<Vec<_>>();
+            for card in veccard_allowed.iter() {
+                if let Some(i_node_child) = vecnode[i_node].child(*card) {
+                    vecnode[i_node_child].n_availability += 1;
+                }
+            }
+            let b_expand = !veccard_unexpanded.is_empty();
+            let (card, i_node_child) = if let Some(&card) = veccard_unexpanded.choose(&mut rng) {
+                let i_node_child = vecnode.len();
+                vecnode.push(SIsmctsNode::new(epi));
+                vecnode[i_node_child].n_availability = 1;
+                vecnode[i_node].vectplcardi_child.push((card, i_node_child));
+                (card, i_node_child)
+            } else {
+                let (card, i_node_child) = unwrap!(veccard_allowed.iter()
+                    .map(|card| (*card, unwrap!(vecnode[i_node].child(*card))))
+                    .max_by(|&(_card_lhs, i_node_lhs), &(_card_rhs, i_node_rhs)| {
+                        let ucb = |i_node: usize| {
+                            let node = &vecnode[i_node];
+                            node.n_payout_sum.as_num::<f64>() / node.n_visits.as_num::<f64>()
+                                + 0.7 * n_payout_abs_max.as_num::<f64>()
+                                    * (node.n_availability.as_num::<f64>().ln() / node.n_visits.as_num::<f64>()).sqrt()
+                        };
+                        unwrap!(ucb(i_node_lhs).partial_cmp(&ucb(i_node_rhs)))
+                    }));
+                (card, i_node_child)
+            };
+            play_card(&mut ahand, &mut stichseq, epi, card);
+            veci_node.push(i_node_child);
+            i_node = i_node_child;
+            if b_expand {
+                break;
+            }
+        }
+        // simulation
+        while !stichseq.game_finished() {
+            let epi = unwrap!(stichseq.current_stich().current_playerindex());
+            let card = *unwrap!(rules.all_allowed_cards(&stichseq, &ahand[epi]).choose(&mut rng));
+            play_card(&mut ahand, &mut stichseq, epi, card);
+        }
+        // backpropagation
+        let an_payout = rules.payout(SStichSequenceGameFinished::new(&stichseq), tpln_stoss_doubling, n_stock);
+        for n_payout in an_payout.iter() {
+            assign_max(&mut n_payout_abs_max, n_payout.abs());
+        }
+        for i_node in veci_node {
+            let node = &mut vecnode[i_node];
+            node.n_visits += 1;
+            node.n_payout_sum += an_payout[node.epi];
+        }
+        if instant_end <= Instant::now() {
+            break;
+        }
+    }
+    // most visited card is considered most robust
+    unwrap!(determinebestcard.veccard_allowed.iter().copied()
+        .max_by_key(|card| vecnode[0].child(*card).map_or(0, |i_node| vecnode[i_node].n_visits)))
+}
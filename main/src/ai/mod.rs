@@ -1,13 +1,15 @@
+pub mod handinference;
 pub mod handiterators;
+pub mod ismcts;
 pub mod rulespecific;
 pub mod suspicion;
 #[cfg(test)]
 pub mod test;
 
-use crate::ai::{handiterators::*, suspicion::*};
+use crate::ai::{handinference::*, handiterators::*, ismcts::*, suspicion::*};
 use crate::game::*;
 use crate::primitives::*;
-use crate::rules::*;
+use crate::rules::{ruleset::*, *};
 use crate::util::*;
 use itertools::Itertools;
 use rand::prelude::*;
@@ -39,12 +41,51 @@ pub enum VAIParams {
     Simulating {
         n_suggest_card_samples: usize,
     },
+    Ismcts {
+        n_iterations: usize,
+        duration: std::time::Duration, // search stops after n_iterations or duration, whichever comes first
+    },
+    Anytime {
+        duration: std::time::Duration, // deadline for determine_best_card_anytime
+    },
+}
+
+pub struct SDoublingParams {
+    pub n_samples: usize, // number of random completions of the partial hand
+    pub f_payout_threshold: f64, // double only if best game is expected to yield more than this
+}
+
+impl Default for SDoublingParams {
+    fn default() -> Self {
+        Self {
+            n_samples: 5,
+            f_payout_threshold: 20.,
+        }
+    }
+}
+
+pub struct SStossDecisionParams {
+    pub n_samples: usize, // number of inferred hands to explore
+    pub n_branches: usize, // maximum number of cards explored per snapshot
+    pub f_payout_threshold: f64, // give Stoss only if it is expected to increase the payout by more than this
+}
+
+impl Default for SStossDecisionParams {
+    fn default() -> Self {
+        Self {
+            n_samples: 5,
+            n_branches: 2,
+            f_payout_threshold: 10.,
+        }
+    }
 }
 
 pub struct SAi {
     n_rank_rules_samples: usize,
     n_suggest_card_branches: usize,
     aiparams: VAIParams,
+    doublingparams: SDoublingParams,
+    stossdecisionparams: SStossDecisionParams,
 }
 
 pub struct SDetermineBestCard<'game> {
@@ -86,6 +127,8 @@ impl SAi {
             n_rank_rules_samples,
             n_suggest_card_branches,
             aiparams: VAIParams::Cheating,
+            doublingparams: SDoublingParams::default(),
+            stossdecisionparams: SStossDecisionParams::default(),
         }
     }
 
@@ -96,13 +139,61 @@ impl SAi {
             aiparams: VAIParams::Simulating {
                 n_suggest_card_samples,
             },
+            doublingparams: SDoublingParams::default(),
+            stossdecisionparams: SStossDecisionParams::default(),
+        }
+    }
+
+    pub fn new_ismcts(n_rank_rules_samples: usize, n_iterations: usize, duration: std::time::Duration) -> Self {
+        SAi {
+            n_rank_rules_samples,
+            n_suggest_card_branches: 0, // irrelevant for ISMCTS
+            aiparams: VAIParams::Ismcts {
+                n_iterations,
+                duration,
+            },
+            doublingparams: SDoublingParams::default(),
+            stossdecisionparams: SStossDecisionParams::default(),
         }
     }
 
+    pub fn new_anytime(n_rank_rules_samples: usize, duration: std::time::Duration) -> Self {
+        SAi {
+            n_rank_rules_samples,
+            n_suggest_card_branches: 0, // irrelevant: branching is increased until deadline
+            aiparams: VAIParams::Anytime {
+                duration,
+            },
+            doublingparams: SDoublingParams::default(),
+            stossdecisionparams: SStossDecisionParams::default(),
+        }
+    }
+
+    pub fn with_doublingparams(self, doublingparams: SDoublingParams) -> Self {
+        SAi{doublingparams, ..self}
+    }
+
+    pub fn with_stossdecisionparams(self, stossdecisionparams: SStossDecisionParams) -> Self {
+        SAi{stossdecisionparams, ..self}
+    }
+
     pub fn rank_rules(&self, hand_fixed: SFullHand, epi_rank: EPlayerIndex, rules: &dyn TRules, tpln_stoss_doubling: (usize, usize), n_stock: isize) -> f64 {
+        self.rules_samples_payouts(hand_fixed, epi_rank, rules, tpln_stoss_doubling, n_stock)
+            .into_iter()
+            .sum::<isize>().as_num::<f64>() / (self.n_rank_rules_samples.as_num::<f64>())
+    }
+
+    // pessimistic counterpart of rank_rules: payout of the worst sample
+    pub fn rank_rules_min(&self, hand_fixed: SFullHand, epi_rank: EPlayerIndex, rules: &dyn TRules, tpln_stoss_doubling: (usize, usize), n_stock: isize) -> isize {
+        unwrap!(self.rules_samples_payouts(hand_fixed, epi_rank, rules, tpln_stoss_doubling, n_stock)
+            .into_iter()
+            .min())
+    }
+
+    fn rules_samples_payouts(&self, hand_fixed: SFullHand, epi_rank: EPlayerIndex, rules: &dyn TRules, tpln_stoss_doubling: (usize, usize), n_stock: isize) -> Vec<isize> {
         // TODO: adjust interface to get whole game in case of VAIParams::Cheating
         let ekurzlang = EKurzLang::from_cards_per_player(hand_fixed.get().len());
-        forever_rand_hands(&SStichSequence::new(ekurzlang), SHand::new_from_iter(hand_fixed.get().iter().copied()), epi_rank, rules)
+        forever_rand_hands(&SStichSequence::new(ekurzlang), SHand::new_from_iter(hand_fixed.get().iter().copied()), epi_rank, /*otplepihand_open*/None, rules)
             .take(self.n_rank_rules_samples)
             .par_bridge() // TODO can we derive a true parallel iterator?
             .map(|mut ahand| {
@@ -120,7 +211,112 @@ impl SAi {
                     &mut SNoVisualization{},
                 ).t_min[epi_rank]
             })
-            .sum::<isize>().as_num::<f64>() / (self.n_rank_rules_samples.as_num::<f64>())
+            .collect()
+    }
+
+    // Expected payout of announcing "Nothing":
+    // If someone else already announced a game, we will (at best) be an opponent in the highest one.
+    // Otherwise, we assume that nobody else wants to play, so that "der Alte muss", Ramsch or Stock applies.
+    pub fn rank_none(
+        &self,
+        epi: EPlayerIndex,
+        hand: SFullHand,
+        gameannouncements: &SGameAnnouncements,
+        tpln_stoss_doubling: (usize, usize),
+        n_stock: isize,
+        ruleset: &SRuleSet,
+    ) -> f64 {
+        if let Some(rules_announced) = gameannouncements.iter()
+            .filter(|&(epi_announced, _orules)| epi_announced!=epi)
+            .filter_map(|(_epi, orules)| orules.as_ref())
+            .min_by_key(|rules| std::cmp::Reverse(rules.priority())) // on equal priority, earlier announcement wins
+        {
+            self.rank_rules(hand, epi, rules_announced.upcast(), tpln_stoss_doubling, n_stock)
+        } else {
+            let rank_stock_or_ramsch = || match &ruleset.stockorramsch {
+                VStockOrT::OrT(rulesramsch) => self.rank_rules(hand, epi, rulesramsch.as_ref(), tpln_stoss_doubling, n_stock),
+                VStockOrT::Stock(n_stock_payment) => -n_stock_payment.as_num::<f64>(),
+            };
+            if !ruleset.b_der_alte_muss {
+                rank_stock_or_ramsch()
+            } else if hand.get().contains(&SCard::new(EFarbe::Eichel, ESchlag::Ober)) {
+                // we must play our best game (if we can play anything at all)
+                allowed_rules(&ruleset.avecrulegroup[epi], hand)
+                    .flatten()
+                    .map(|rules| self.rank_rules(hand, epi, rules.upcast(), tpln_stoss_doubling, n_stock))
+                    .reduce(f64::max)
+                    .unwrap_or_else(rank_stock_or_ramsch)
+            } else {
+                0. // someone else must play a game that we cannot foresee
+            }
+        }
+    }
+
+    // Sample the cards not yet seen and double if the best game on the completed hand is promising.
+    pub fn suggest_doubling(&self, epi: EPlayerIndex, slccard_first: &[SCard], ruleset: &SRuleSet, n_stock: isize) -> bool {
+        let ekurzlang = ruleset.ekurzlang;
+        let veccard_unseen = SCard::values(ekurzlang)
+            .filter(|card| !slccard_first.contains(card))
+            .collect::<Vec<_>>();
+        let f_payout_avg = (0..self.doublingparams.n_samples)
+            .map(|_i_sample| {
+                let veccard = slccard_first.iter()
+                    .chain(veccard_unseen.choose_multiple(&mut rand::thread_rng(), ekurzlang.cards_per_player()-slccard_first.len()))
+                    .copied()
+                    .collect::<Vec<_>>();
+                let hand = SFullHand::new(&veccard, ekurzlang);
+                allowed_rules(&ruleset.avecrulegroup[epi], hand)
+                    .flatten()
+                    .map(|rules| self.rank_rules(
+                        hand,
+                        /*epi_rank*/rules.active_playerindex(),
+                        rules.upcast(),
+                        /*tpln_stoss_doubling*/(0, 0),
+                        n_stock,
+                    ))
+                    .fold(
+                        // no active game => nobody announced anything yet, so Ramsch or Stock applies
+                        self.rank_none(
+                            epi,
+                            hand,
+                            &SGameAnnouncements::new(SStaticEPI0{}),
+                            /*tpln_stoss_doubling*/(0, 0),
+                            n_stock,
+                            ruleset,
+                        ),
+                        f64::max,
+                    )
+            })
+            .sum::<f64>() / self.doublingparams.n_samples.as_num::<f64>();
+        f_payout_avg > self.doublingparams.f_payout_threshold
+    }
+
+    // Explore hands inferred from announcements, Stoss and cards played so far, comparing the payouts with and without another Stoss by epi.
+    // Covers opponents as well as re-Stoss by the declaring party.
+    pub fn suggest_stoss(&self, game: &SGame, epi: EPlayerIndex) -> bool {
+        let (n_stoss, n_doubling) = stoss_and_doublings(&game.vecstoss, &game.doublings);
+        let SStossDecisionParams{n_samples, n_branches, f_payout_threshold} = self.stossdecisionparams;
+        let f_payout_gain_avg = forever_rand_hands_inferred(game, epi)
+            .take(n_samples)
+            .par_bridge() // TODO can we derive a true parallel iterator?
+            .map(|ahand| {
+                let payout = |n_stoss_payout| explore_snapshots(
+                    &mut ahand.clone(),
+                    game.rules.as_ref(),
+                    &mut game.stichseq.clone(),
+                    &branching_factor(|_stichseq| (1, n_branches+1)),
+                    &SMinReachablePayoutAlphaBeta::new(
+                        game.rules.as_ref(),
+                        epi,
+                        /*tpln_stoss_doubling*/(n_stoss_payout, n_doubling),
+                        game.n_stock,
+                    ),
+                    &mut SNoVisualization,
+                ).t_min[epi];
+                payout(n_stoss+1) - payout(n_stoss)
+            })
+            .sum::<isize>().as_num::<f64>() / n_samples.as_num::<f64>();
+        f_payout_gain_avg > f_payout_threshold
     }
 
     pub fn suggest_card<SnapshotVisualizer: TSnapshotVisualizer<SMinMax>>(
@@ -130,13 +326,48 @@ impl SAi {
     ) -> SCard {
         let determinebestcard = SDetermineBestCard::new_from_game(game);
         let epi_fixed = determinebestcard.epi_fixed;
+        let eremainingcards = unwrap!(ERemainingCards::checked_from_usize(
+            remaining_cards_per_hand(determinebestcard.stichseq)[epi_fixed] - 1 // ERemainingCards starts with 1
+        ));
+        use ERemainingCards::*;
         if let Some(card)=determinebestcard.single_allowed_card() {
             card
         } else if let Some(card) = game.rules.rulespecific_ai()
+            // rule-specific heuristics only replace the filtered search, not the exact one
+            .filter(|_airulespecific| !matches!(eremainingcards, _1|_2|_3|_4))
             .and_then(|airulespecific| airulespecific.suggest_card(game))
         {
             card
         } else {
+            let tpln_stoss_doubling = stoss_and_doublings(&game.vecstoss, &game.doublings);
+            let itahand_rand = || forever_rand_hands_inferred(game, epi_fixed);
+            let on_suggest_card_samples = match self.aiparams {
+                VAIParams::Cheating => None,
+                VAIParams::Simulating{n_suggest_card_samples} => Some(n_suggest_card_samples),
+                VAIParams::Ismcts{n_iterations, duration} => {
+                    return ismcts(
+                        &determinebestcard,
+                        itahand_rand().take(n_iterations),
+                        tpln_stoss_doubling,
+                        game.n_stock,
+                        duration,
+                    );
+                },
+                VAIParams::Anytime{duration} => {
+                    return *unwrap!(determine_best_card_anytime(
+                        &determinebestcard,
+                        itahand_rand(),
+                        &SMinReachablePayoutLowerBoundViaHint::new(
+                            determinebestcard.rules,
+                            epi_fixed,
+                            tpln_stoss_doubling,
+                            game.n_stock,
+                        ),
+                        std::time::Instant::now() + duration,
+                        std::time::Instant::now,
+                    ).determinebestcardresult.cards_with_maximum_value().0.first());
+                },
+            };
             macro_rules! forward_to_determine_best_card{(
                 ($func_filter_allowed_cards: expr, $foreachsnapshot: ident,),
                 $itahand: expr,
@@ -148,16 +379,12 @@ impl SAi {
                     &$foreachsnapshot::new(
                         determinebestcard.rules,
                         epi_fixed,
-                        /*tpln_stoss_doubling*/stoss_and_doublings(&game.vecstoss, &game.doublings),
+                        tpln_stoss_doubling,
                         game.n_stock,
                     ),
                     fn_visualizer,
                 )
             }}}
-            let eremainingcards = unwrap!(ERemainingCards::checked_from_usize(
-                remaining_cards_per_hand(determinebestcard.stichseq)[epi_fixed] - 1 // ERemainingCards starts with 1
-            ));
-            use ERemainingCards::*;
             *unwrap!(cartesian_match!(
                 forward_to_determine_best_card,
                 match (eremainingcards) {
@@ -176,16 +403,15 @@ impl SAi {
                         SMinReachablePayoutLowerBoundViaHint,
                     ),
                 },
-                match ((&self.aiparams, eremainingcards)) {
-                    (&VAIParams::Cheating, _) => {
+                match ((on_suggest_card_samples, eremainingcards)) {
+                    (None, _) => { // cheating
                         std::iter::once(game.ahand.clone())
                     },
-                    (&VAIParams::Simulating{n_suggest_card_samples:_}, _1|_2|_3|_4) => {
-                        all_possible_hands(determinebestcard.stichseq, determinebestcard.hand_fixed.clone(), epi_fixed, determinebestcard.rules)
+                    (Some(_n_suggest_card_samples), _1|_2|_3|_4) => {
+                        all_possible_hands_inferred(game, epi_fixed).into_iter()
                     },
-                    (&VAIParams::Simulating{n_suggest_card_samples}, _5|_6|_7|_8) =>{ 
-                        forever_rand_hands(determinebestcard.stichseq, determinebestcard.hand_fixed.clone(), epi_fixed, determinebestcard.rules)
-                            .take(n_suggest_card_samples)
+                    (Some(n_suggest_card_samples), _5|_6|_7|_8) =>{ 
+                        itahand_rand().take(n_suggest_card_samples)
                     },
                 },
             ).cards_with_maximum_value().0.first())
@@ -204,6 +430,7 @@ pub fn unplayed_cards<'lifetime>(stichseq: &'lifetime SStichSequence, hand_fixed
 }
 
 #[test]
+#[cfg(not(feature="dreier"))]
 fn test_unplayed_cards() {
     use crate::card::card_values::*;
     let mut stichseq = SStichSequence::new(EKurzLang::Lang);
@@ -231,18 +458,40 @@ impl<T> SDetermineBestCardResult<T> {
             .map(move |card| (*card, unwrap!(self.mapcardt[*card].as_ref())))
     }
     pub fn cards_with_maximum_value(&self) -> (Vec<SCard>, &T) where T: std::fmt::Debug + Ord {
-        let veccard = self.veccard_allowed.iter().copied()
-            .max_set_by_key(|card| unwrap!(self.mapcardt[*card].as_ref()));
+        self.cards_with_maximum_value_by(Ord::cmp)
+    }
+    pub fn cards_with_maximum_value_by(&self, fn_cmp: impl Fn(&T, &T)->std::cmp::Ordering) -> (Vec<SCard>, &T) where T: std::fmt::Debug {
+        let t_max = unwrap!(self.cards_and_ts()
+            .map(|(_card, t)| t)
+            .max_by(|t_lhs, t_rhs| fn_cmp(t_lhs, t_rhs)));
+        let veccard = self.cards_and_ts()
+            .filter(|(_card, t)| std::cmp::Ordering::Equal==fn_cmp(t, t_max))
+            .map(|(card, _t)| card)
+            .collect::<Vec<_>>();
         assert!(!veccard.is_empty());
         let t = unwrap!(self.mapcardt[veccard[0]].as_ref());
         (veccard, t)
     }
 }
 
+impl SDetermineBestCardResult<SPayoutStatsPerStrategy> {
+    fn accumulate(&mut self, determinebestcardresult: &Self) {
+        assert_eq!(self.veccard_allowed, determinebestcardresult.veccard_allowed);
+        for card in self.veccard_allowed.iter() {
+            match (&mut self.mapcardt[*card], &determinebestcardresult.mapcardt[*card]) {
+                (_, None) => {},
+                (ot @ None, Some(t)) => *ot = Some(t.clone()),
+                (Some(t_self), Some(t)) => t_self.accumulate(t),
+            }
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct SPayoutStats {
     n_min: isize,
     n_sum: isize,
+    n_sum_sq: isize,
     n_max: isize,
     n_count: usize,
 }
@@ -253,6 +502,7 @@ impl SPayoutStats {
             n_min: n_payout,
             n_max: n_payout,
             n_sum: n_payout,
+            n_sum_sq: n_payout * n_payout,
             n_count: 1,
         }
     }
@@ -261,6 +511,7 @@ impl SPayoutStats {
         assign_min(&mut self.n_min, paystats.n_min);
         assign_max(&mut self.n_max, paystats.n_max);
         self.n_sum += paystats.n_sum;
+        self.n_sum_sq += paystats.n_sum_sq;
         self.n_count += paystats.n_count;
     }
 
@@ -270,12 +521,40 @@ impl SPayoutStats {
     pub fn max(&self) -> isize {
         self.n_max
     }
+    pub fn count(&self) -> usize {
+        self.n_count
+    }
     pub fn avg(&self) -> f32 {
         self.n_sum.as_num::<f32>() / self.n_count.as_num::<f32>()
     }
+    pub fn stddev(&self) -> f32 {
+        let f_avg = self.avg();
+        (self.n_sum_sq.as_num::<f32>() / self.n_count.as_num::<f32>() - f_avg * f_avg).max(0.).sqrt()
+    }
+}
+
+// how to rank cards given their payouts over all explored hands
+#[derive(Clone, Copy, Debug)]
+pub enum VPayoutRanking {
+    MinThenAvg, // prefer cards that win in all explored hands, then the highest average
+    Expected, // highest average
+    RiskAdjusted(f32), // highest average, penalized by the given multiple of the standard deviation
+}
+
+impl std::str::FromStr for VPayoutRanking {
+    type Err = Error;
+    fn from_str(str_payoutranking: &str) -> Result<Self, Self::Err> {
+        match str_payoutranking.split_once(':') {
+            None if "min"==str_payoutranking => Ok(VPayoutRanking::MinThenAvg),
+            None if "expected"==str_payoutranking => Ok(VPayoutRanking::Expected),
+            None if "risk"==str_payoutranking => Ok(VPayoutRanking::RiskAdjusted(1.)),
+            Some(("risk", str_risk_aversion)) => Ok(VPayoutRanking::RiskAdjusted(str_risk_aversion.trim().parse()?)),
+            _ => Err(format_err!("Could not parse payout ranking {} (expected min, expected, risk or risk:<factor>)", str_payoutranking)),
+        }
+    }
 }
 
-type SPayoutStatsPerStrategy = SPerMinMaxStrategy<SPayoutStats>;
+pub type SPayoutStatsPerStrategy = SPerMinMaxStrategy<SPayoutStats>;
 
 impl SPayoutStatsPerStrategy {
     fn accumulate(&mut self, paystats: &Self) {
@@ -284,6 +563,35 @@ impl SPayoutStatsPerStrategy {
         self.t_selfish_max.accumulate(&paystats.t_selfish_max);
         self.t_max.accumulate(&paystats.t_max);
     }
+
+    pub fn compare(&self, other: &Self, payoutranking: VPayoutRanking) -> std::cmp::Ordering {
+        use std::cmp::Ordering::*;
+        let cmp_avg = |fn_value: &dyn Fn(&SPayoutStats)->f32| unwrap!(fn_value(&self.t_min).partial_cmp(&fn_value(&other.t_min)))
+            .then_with(|| unwrap!(self.t_selfish_min.avg().partial_cmp(&other.t_selfish_min.avg())));
+        match payoutranking {
+            VPayoutRanking::MinThenAvg => {
+                let n_min_self = self.t_min.n_min;
+                let n_min_other = other.t_min.n_min;
+                // TODO improve logic wrt t_selfish_min/t_selfish_max/t_max
+                match (n_min_self.cmp(&0), n_min_other.cmp(&0)) {
+                    (Greater, Greater) => match n_min_self.cmp(&n_min_other) {
+                        Equal => unwrap!(self.t_selfish_min.avg().partial_cmp(&other.t_selfish_min.avg())),
+                        Greater => Greater,
+                        Less => Less,
+                    },
+                    (Greater, _) => Greater,
+                    (_, Greater) => Less,
+                    (Equal, Less) => Greater,
+                    (Less, Equal) => Less,
+                    (Less, Less)|(Equal, Equal) => {
+                        unwrap!(self.t_selfish_min.avg().partial_cmp(&other.t_selfish_min.avg()))
+                    },
+                }
+            },
+            VPayoutRanking::Expected => cmp_avg(&SPayoutStats::avg),
+            VPayoutRanking::RiskAdjusted(f_risk_aversion) => cmp_avg(&|paystats| paystats.avg() - f_risk_aversion * paystats.stddev()),
+        }
+    }
 }
 
 impl std::cmp::PartialOrd for SPayoutStatsPerStrategy {
@@ -293,24 +601,7 @@ impl std::cmp::PartialOrd for SPayoutStatsPerStrategy {
 }
 impl std::cmp::Ord for SPayoutStatsPerStrategy {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        use std::cmp::Ordering::*;
-        let n_min_self = self.t_min.n_min;
-        let n_min_other = other.t_min.n_min;
-        // TODO improve logic wrt t_selfish_min/t_selfish_max/t_max
-        match (n_min_self.cmp(&0), n_min_other.cmp(&0)) {
-            (Greater, Greater) => match n_min_self.cmp(&n_min_other) {
-                Equal => unwrap!(self.t_selfish_min.avg().partial_cmp(&other.t_selfish_min.avg())),
-                Greater => Greater,
-                Less => Less,
-            },
-            (Greater, _) => Greater,
-            (_, Greater) => Less,
-            (Equal, Less) => Greater,
-            (Less, Equal) => Less,
-            (Less, Less)|(Equal, Equal) => {
-                unwrap!(self.t_selfish_min.avg().partial_cmp(&other.t_selfish_min.avg()))
-            },
-        }
+        self.compare(other, VPayoutRanking::MinThenAvg)
     }
 }
 
@@ -379,6 +670,77 @@ pub fn determine_best_card<
     }
 }
 
+pub struct SDetermineBestCardAnytimeResult {
+    pub determinebestcardresult: SDetermineBestCardResult<SPayoutStatsPerStrategy>,
+    // confidence: the more hands and the less restricted the branching, the more reliable the result
+    pub n_hands: usize,
+    pub n_branches: usize, // n_branches >= cards in hand means that branching was not restricted
+}
+
+// Explores hands from itahand in rounds, deepening (i.e. increasing branching factor) as long as
+// the deadline permits, and accumulates results over all rounds.
+// Stops before the deadline if the next round is not expected to finish in time (or itahand is exhausted).
+// Explorations still running at the deadline only follow a single card per snapshot.
+pub fn determine_best_card_anytime<ForEachSnapshot: TForEachSnapshot<Output=SMinMax> + Sync>(
+    determinebestcard: &SDetermineBestCard,
+    itahand: impl Iterator<Item=EnumMap<EPlayerIndex, SHand>> + Send,
+    foreachsnapshot: &ForEachSnapshot,
+    instant_deadline: std::time::Instant,
+    fn_now: impl Fn()->std::time::Instant + Sync, // usually std::time::Instant::now
+) -> SDetermineBestCardAnytimeResult
+    where
+        ForEachSnapshot::Output: std::fmt::Debug + Send,
+{
+    let n_cards_hand = determinebestcard.hand_fixed.cards().len();
+    let n_hands_per_round = rayon::current_num_threads(); // keep all threads busy
+    let mut itahand = itahand.peekable();
+    assert!(itahand.peek().is_some());
+    let mut explore_round = |n_branches: usize| {
+        let func_filter_allowed_cards = branching_factor(move |_stichseq| (1, n_branches+1));
+        let func_filter_allowed_cards_single = branching_factor(|_stichseq| (1, 2));
+        let mut n_hands_round = 0;
+        let determinebestcardresult_round = determine_best_card(
+            determinebestcard,
+            itahand.by_ref().take(n_hands_per_round).inspect(|_ahand| n_hands_round += 1),
+            &|stichseq: &SStichSequence, veccard_allowed: &mut SHandVector| {
+                if instant_deadline <= fn_now() {
+                    // finish running explorations quickly
+                    func_filter_allowed_cards_single(stichseq, veccard_allowed);
+                } else if n_branches < n_cards_hand {
+                    func_filter_allowed_cards(stichseq, veccard_allowed);
+                }
+            },
+            foreachsnapshot,
+            |_,_,_| SNoVisualization,
+        );
+        (determinebestcardresult_round, n_hands_round, itahand.peek().is_some())
+    };
+    let mut n_branches = 1;
+    let mut instant_round = fn_now();
+    let (mut determinebestcardresult, mut n_hands, mut b_hands_left) = explore_round(n_branches);
+    while b_hands_left {
+        let instant_now = fn_now();
+        let duration_round = instant_now - instant_round;
+        if instant_deadline < instant_now + duration_round {
+            break;
+        }
+        // heuristic: deeper round takes considerably longer
+        if n_branches < n_cards_hand && instant_now + 4 * duration_round <= instant_deadline {
+            n_branches += 1;
+        }
+        instant_round = instant_now;
+        let (determinebestcardresult_round, n_hands_round, b_hands_left_round) = explore_round(n_branches);
+        determinebestcardresult.accumulate(&determinebestcardresult_round);
+        n_hands += n_hands_round;
+        b_hands_left = b_hands_left_round;
+    }
+    SDetermineBestCardAnytimeResult {
+        determinebestcardresult,
+        n_hands,
+        n_branches,
+    }
+}
+
 pub fn branching_factor(fn_stichseq_to_intvl: impl Fn(&SStichSequence)->(usize, usize)) -> impl Fn(&SStichSequence, &mut SHandVector) {
     move |stichseq, veccard_allowed| {
         assert!(!veccard_allowed.is_empty());
@@ -393,6 +755,7 @@ pub fn branching_factor(fn_stichseq_to_intvl: impl Fn(&SStichSequence)->(usize,
 }
 
 #[test]
+#[cfg(not(feature="dreier"))]
 fn test_is_compatible_with_game_so_far() {
     use crate::rules::rulesrufspiel::*;
     use crate::rules::payoutdecider::*;
@@ -412,6 +775,8 @@ fn test_is_compatible_with_game_so_far() {
             SDoublings::new(SStaticEPI0{}),
             Some(SStossParams::new( // TODO implement tests for SStoss
                 /*n_stoss_max*/ 4,
+                EStossTime::BeforeFirstCard,
+                /*b_announce_schneider_schwarz*/ false,
             )),
             rules.box_clone(),
             /*n_stock*/ 0,
@@ -437,6 +802,7 @@ fn test_is_compatible_with_game_so_far() {
                 &game.stichseq,
                 game.ahand[unwrap!(game.which_player_can_do_something()).0].clone(),
                 unwrap!(game.which_player_can_do_something()).0,
+                game.open_hand(),
                 game.rules.as_ref(),
             )
                 .take(100)
@@ -483,8 +849,8 @@ fn test_is_compatible_with_game_so_far() {
     );
 }
 
-#[test]
-fn test_very_expensive_exploration() { // this kind of abuses the test mechanism to benchmark the performance
+#[cfg(all(test, not(feature="dreier")))]
+fn explore_solo_endgame(func_filter_allowed_cards: &(impl Fn(&SStichSequence, &mut SHandVector) + std::marker::Sync)) {
     use crate::card::card_values::*;
     use crate::game::*;
     use crate::rules::{ruleset::*, rulessolo::*, payoutdecider::*};
@@ -502,6 +868,8 @@ fn test_very_expensive_exploration() { // this kind of abuses the test mechanism
         SDoublings::new(SStaticEPI0{}),
         Some(SStossParams::new(
             /*n_stoss_max*/ 4,
+            EStossTime::BeforeFirstCard,
+            /*b_announce_schneider_schwarz*/ false,
         )),
         TRulesBoxClone::box_clone(sololike(
             epi_active,
@@ -521,6 +889,7 @@ fn test_very_expensive_exploration() { // this kind of abuses the test mechanism
         &game.stichseq,
         game.ahand[epi_active].clone(),
         epi_active,
+        game.open_hand(),
         game.rules.as_ref(),
     ) {
         assert!(!game.current_playable_stich().is_full());
@@ -528,7 +897,7 @@ fn test_very_expensive_exploration() { // this kind of abuses the test mechanism
         let determinebestcardresult = determine_best_card(
             &determinebestcard,
             std::iter::once(ahand),
-            /*func_filter_allowed_cards*/&branching_factor(|_stichseq| (1, 2)),
+            func_filter_allowed_cards,
             &SMinReachablePayout::new_from_game(&game),
             /*fn_visualizer*/|_,_,_| SNoVisualization,
         );
@@ -553,3 +922,16 @@ fn test_very_expensive_exploration() { // this kind of abuses the test mechanism
         }
     }
 }
+
+#[test]
+#[cfg(not(feature="dreier"))]
+fn test_very_expensive_exploration() {
+    explore_solo_endgame(&branching_factor(|_stichseq| (1, 2)));
+}
+
+#[test]
+#[ignore] // run via cargo test -- --ignored
+#[cfg(not(feature="dreier"))]
+fn bench_exhaustive_exploration() { // this kind of abuses the test mechanism to benchmark the performance
+    explore_solo_endgame(&|_stichseq, _veccard_allowed| {/*no filtering*/});
+}
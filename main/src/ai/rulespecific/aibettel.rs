@@ -0,0 +1,39 @@
+use crate::ai::{rulespecific::*, unplayed_cards};
+use crate::game::*;
+use crate::primitives::*;
+use crate::rules::*;
+use crate::util::*;
+
+#[derive(new)]
+pub struct SAIBettel<'rules> {
+    rules : &'rules dyn TRules,
+    epi_active : EPlayerIndex,
+}
+
+impl TRuleSpecificAI for SAIBettel<'_> {
+    fn suggest_card(&self, game: &SGame) -> Option<SCard> {
+        let epi = unwrap!(game.which_player_can_do_something()).0;
+        let rules = self.rules;
+        let stichseq = &game.stichseq;
+        let hand = &game.ahand[epi];
+        let stich = stichseq.current_stich();
+        let b_declarer_last = epi==self.epi_active && stich.size()==EPlayerIndex::SIZE-1;
+        // force declarer: leave him the stich and keep low cards for later
+        let b_declarer_wins = epi!=self.epi_active
+            && stich.get(self.epi_active).is_some()
+            && self.epi_active==rules.preliminary_winner_index(stich);
+        if b_declarer_last || b_declarer_wins {
+            // get rid of the most dangerous card that does not win the stich
+            rules.all_allowed_cards(stichseq, hand).into_iter()
+                .filter(|card| !wins_current_stich(rules, stichseq, *card))
+                .max_by_key(|card| count_beaten_cards(rules, *card, unplayed_cards(stichseq, hand)))
+        } else {
+            None
+        }
+    }
+
+    fn order_cards(&self, epi: EPlayerIndex, _stichseq: &SStichSequence, ahand: &EnumMap<EPlayerIndex, SHand>, veccard: &mut SHandVector) {
+        // play low
+        veccard.sort_by_cached_key(|card| count_beaten_cards(self.rules, *card, cards_of_other_players(ahand, epi)));
+    }
+}
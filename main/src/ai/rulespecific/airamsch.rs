@@ -0,0 +1,60 @@
+use crate::ai::rulespecific::*;
+use crate::game::*;
+use crate::primitives::*;
+use crate::rules::{card_points::points_card, rulesramsch::*, *};
+use crate::util::*;
+use itertools::Itertools;
+
+#[derive(new)]
+pub struct SAIRamsch<'rules> {
+    rules : &'rules SRulesRamsch,
+}
+
+impl SAIRamsch<'_> {
+    // player who won all stichs so far, provided that enough stichs are played to take him seriously
+    fn epi_durchmarsch(&self, stichseq: &SStichSequence) -> Option<EPlayerIndex> {
+        match self.rules.durchmarsch() {
+            VDurchmarsch::None => None,
+            VDurchmarsch::All | VDurchmarsch::AtLeast(_) => {
+                if stichseq.completed_stichs().len() < stichseq.kurzlang().cards_per_player() / 2 {
+                    None
+                } else {
+                    stichseq.completed_stichs_winner_index(self.rules)
+                        .map(|(_stich, epi_winner)| epi_winner)
+                        .dedup()
+                        .exactly_one()
+                        .ok()
+                }
+            },
+        }
+    }
+}
+
+impl TRuleSpecificAI for SAIRamsch<'_> {
+    fn suggest_card(&self, game: &SGame) -> Option<SCard> {
+        let epi = unwrap!(game.which_player_can_do_something()).0;
+        let rules = self.rules;
+        let stichseq = &game.stichseq;
+        // only the last player in a stich knows for sure who wins it
+        if stichseq.current_stich().size()==EPlayerIndex::SIZE-1 {
+            let (veccard_win, veccard_lose): (Vec<_>, Vec<_>) = rules.all_allowed_cards(stichseq, &game.ahand[epi]).into_iter()
+                .partition(|card| wins_current_stich(rules, stichseq, *card));
+            if self.epi_durchmarsch(stichseq).is_some_and(|epi_durchmarsch|
+                epi_durchmarsch!=epi && epi_durchmarsch==rules.preliminary_winner_index(stichseq.current_stich())
+            ) {
+                // stop Durchmarsch as cheaply as possible
+                if let Some(card) = veccard_win.into_iter().min_by_key(|card| points_card(*card)) {
+                    return Some(card);
+                }
+            }
+            // do not take points, but get rid of as many as possible
+            return veccard_lose.into_iter().max_by_key(|card| points_card(*card));
+        }
+        None
+    }
+
+    fn order_cards(&self, epi: EPlayerIndex, _stichseq: &SStichSequence, ahand: &EnumMap<EPlayerIndex, SHand>, veccard: &mut SHandVector) {
+        // weak cards first
+        veccard.sort_by_cached_key(|card| count_beaten_cards(self.rules, *card, cards_of_other_players(ahand, epi)));
+    }
+}
@@ -14,11 +14,12 @@ impl TRuleSpecificAI for SAIRufspiel<'_> {
         let epi = unwrap!(game.which_player_can_do_something()).0;
         let rules = self.rules;
         // suchen
-        if epi!=rules.active_playerindex() && game.stichseq.no_card_played() {
+        // With Ruf-Zehn, the player holds the Sau and would simply take the searched Zehn, so we do not search.
+        if epi!=rules.active_playerindex() && game.stichseq.no_card_played() && !rules.is_ruf_zehn() {
             let hand = &game.ahand[epi];
-            if !hand.contains(rules.rufsau()) {
+            if !hand.contains(rules.rufkarte()) {
                 let veccard_ruffarbe : Vec<_> = hand.cards().iter().copied()
-                    .filter(|&card| rules.trumpforfarbe(card)==rules.trumpforfarbe(rules.rufsau()))
+                    .filter(|&card| rules.trumpforfarbe(card)==rules.trumpforfarbe(rules.rufkarte()))
                     .collect();
                 match (veccard_ruffarbe.len(), game.kurzlang()) {
                     (0, _kurzlang) => return None,
@@ -30,6 +31,13 @@ impl TRuleSpecificAI for SAIRufspiel<'_> {
                 }
             }
         }
+        // Ruf-Zehn: the player fetches the Zehn with his Sau
+        if epi==rules.active_playerindex() && game.stichseq.no_card_played() && rules.is_ruf_zehn() {
+            let card_sau = SCard::new(rules.rufkarte().farbe(), ESchlag::Ass);
+            if game.ahand[epi].contains(card_sau) {
+                return Some(card_sau);
+            }
+        }
         None
     }
 }
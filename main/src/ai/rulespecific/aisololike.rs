@@ -0,0 +1,60 @@
+use crate::ai::{rulespecific::*, unplayed_cards};
+use crate::game::*;
+use crate::primitives::*;
+use crate::rules::{card_points::points_card, *};
+use crate::util::*;
+
+// Solo, Wenz, Geier and their Farb-variants
+#[derive(new)]
+pub struct SAISoloLike<'rules> {
+    rules : &'rules dyn TRules,
+    epi_active : EPlayerIndex,
+}
+
+impl SAISoloLike<'_> {
+    // declarer already played and does not win the current stich, so opponents get it anyway
+    fn stich_safe_for_opponents(&self, stichseq: &SStichSequence) -> bool {
+        let stich = stichseq.current_stich();
+        stich.get(self.epi_active).is_some()
+            && self.epi_active!=self.rules.preliminary_winner_index(stich)
+    }
+}
+
+impl TRuleSpecificAI for SAISoloLike<'_> {
+    fn suggest_card(&self, game: &SGame) -> Option<SCard> {
+        let epi = unwrap!(game.which_player_can_do_something()).0;
+        let rules = self.rules;
+        let stichseq = &game.stichseq;
+        let hand = &game.ahand[epi];
+        if epi==self.epi_active {
+            // draw trumpf: lead the highest trumpf as long as opponents may have some
+            if stichseq.current_stich().is_empty() {
+                let veccard_trumpf_unplayed = unplayed_cards(stichseq, hand)
+                    .filter(|card| rules.trumpforfarbe(*card).is_trumpf())
+                    .collect::<Vec<_>>();
+                if !veccard_trumpf_unplayed.is_empty() {
+                    return hand.cards().iter().copied()
+                        .filter(|card| rules.trumpforfarbe(*card).is_trumpf())
+                        .max_by(|card_lhs, card_rhs| unwrap!(rules.compare_cards(*card_lhs, *card_rhs)))
+                        .filter(|card| veccard_trumpf_unplayed.len()==count_beaten_cards(rules, *card, veccard_trumpf_unplayed.iter().copied()));
+                }
+            }
+        } else if self.stich_safe_for_opponents(stichseq) {
+            // schmieren: give as many points as possible, but keep trumpf
+            return rules.all_allowed_cards(stichseq, hand).into_iter()
+                .max_by_key(|card| (!rules.trumpforfarbe(*card).is_trumpf(), points_card(*card)));
+        }
+        None
+    }
+
+    fn order_cards(&self, epi: EPlayerIndex, stichseq: &SStichSequence, ahand: &EnumMap<EPlayerIndex, SHand>, veccard: &mut SHandVector) {
+        if epi==self.epi_active {
+            // declarer: strong cards first
+            veccard.sort_by_cached_key(|card| std::cmp::Reverse(
+                count_beaten_cards(self.rules, *card, cards_of_other_players(ahand, epi))
+            ));
+        } else if self.stich_safe_for_opponents(stichseq) {
+            veccard.sort_by_cached_key(|card| std::cmp::Reverse(points_card(*card)));
+        }
+    }
+}
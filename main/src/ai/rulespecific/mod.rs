@@ -1,7 +1,37 @@
+pub mod aibettel;
+pub mod airamsch;
 pub mod airufspiel;
+pub mod aisololike;
 use crate::game::*;
 use crate::primitives::*;
+use crate::rules::*;
+use crate::util::*;
+use std::cmp::Ordering;
 
 pub trait TRuleSpecificAI {
+    // fast path: card to be played without searching
     fn suggest_card(&self, game: &SGame) -> Option<SCard>;
+    // move ordering: promising cards first, so that search can prune more
+    fn order_cards(&self, _epi: EPlayerIndex, _stichseq: &SStichSequence, _ahand: &EnumMap<EPlayerIndex, SHand>, _veccard: &mut SHandVector) {}
+}
+
+// would epi be the preliminary winner of the current stich after playing card?
+fn wins_current_stich(rules: &dyn TRules, stichseq: &SStichSequence, card: SCard) -> bool {
+    let mut stich = stichseq.current_stich().clone();
+    let epi = unwrap!(stich.current_playerindex());
+    stich.push(card);
+    epi==rules.preliminary_winner_index(&stich)
+}
+
+// number of other cards that card would beat (i.e. how dangerous it is to keep it)
+fn count_beaten_cards(rules: &dyn TRules, card: SCard, itcard_other: impl Iterator<Item=SCard>) -> usize {
+    itcard_other
+        .filter(|card_other| Some(Ordering::Greater)==rules.compare_cards(card, *card_other))
+        .count()
+}
+
+fn cards_of_other_players(ahand: &EnumMap<EPlayerIndex, SHand>, epi: EPlayerIndex) -> impl Iterator<Item=SCard> + '_ {
+    EPlayerIndex::values()
+        .filter(move |epi_other| *epi_other!=epi)
+        .flat_map(move |epi_other| ahand[epi_other].cards().iter().copied())
 }
@@ -1,19 +1,26 @@
+use crate::ai::rulespecific::TRuleSpecificAI;
 use crate::game::{stoss_and_doublings, SGame, SStichSequence};
 use crate::primitives::*;
 use crate::rules::*;
 use crate::util::*;
+use serde::Serialize;
 use itertools::Itertools;
 use rand::{self, Rng};
-use std::{cmp::Ordering, fmt, fs, io::Write};
+use std::{cmp::Ordering, collections::HashMap, fmt, fs, hash::Hash, io::Write};
 
 pub trait TForEachSnapshot {
     type Output;
+    type Bounds: Clone + Default + Eq + Hash; // passed from a snapshot to its successors (e.g. alpha-beta window)
+    const B_KNOWN_PARTIES: bool = false; // whether combine_outputs needs the parties (determining them is not free)
     fn final_output(&self, slcstich: SStichSequenceGameFinished, rulestatecache: &SRuleStateCache) -> Self::Output;
-    fn pruned_output(&self, stichseq: &SStichSequence, ahand: &EnumMap<EPlayerIndex, SHand>, rulestatecache: &SRuleStateCache) -> Option<Self::Output>;
-    fn combine_outputs<ItTplCardOutput: Iterator<Item=(SCard, Self::Output)>>(
+    fn pruned_output(&self, bounds: &Self::Bounds, stichseq: &SStichSequence, ahand: &EnumMap<EPlayerIndex, SHand>, rulestatecache: &SRuleStateCache) -> Option<Self::Output>;
+    fn combine_outputs<FnExploreCard: FnMut(SCard, Self::Bounds)->Self::Output>(
         &self,
         epi_card: EPlayerIndex,
-        ittplcardoutput: ItTplCardOutput,
+        oknownparties: Option<&SKnownParties>, // Some iff B_KNOWN_PARTIES
+        bounds: &Self::Bounds,
+        veccard_allowed: SHandVector,
+        fn_explore_card: FnExploreCard,
     ) -> Self::Output;
 }
 
@@ -93,9 +100,9 @@ pub fn player_table<T: fmt::Display>(epi_self: EPlayerIndex, fn_per_player: impl
           <tr><td>{}</td><td>{}</td></tr>
           <tr><td colspan=\"2\">{}</td></tr>
         </table>\n",
-        fn_per_player_internal(EPlayerIndex::EPI2),
+        EPlayerIndex::values().skip(2).take(EPlayerIndex::SIZE-3).map(&fn_per_player_internal).join("<br>"), // vis-a-vis (if any)
         fn_per_player_internal(EPlayerIndex::EPI1),
-        fn_per_player_internal(EPlayerIndex::EPI3),
+        fn_per_player_internal(EPlayerIndex::wrapped_from_usize(EPlayerIndex::SIZE-1)),
         fn_per_player_internal(EPlayerIndex::EPI0),
     )
 }
@@ -162,6 +169,7 @@ pub fn explore_snapshots<ForEachSnapshot>(
 ) -> ForEachSnapshot::Output 
     where
         ForEachSnapshot: TForEachSnapshot,
+        ForEachSnapshot::Output: Clone,
 {
     explore_snapshots_internal(
         ahand,
@@ -173,29 +181,92 @@ pub fn explore_snapshots<ForEachSnapshot>(
         ),
         stichseq,
         func_filter_allowed_cards,
+        rules.rulespecific_ai().as_deref(),
         foreachsnapshot,
         snapshotvisualizer,
+        &mut HashMap::new(),
+        ForEachSnapshot::Bounds::default(),
     )
 }
 
+#[derive(PartialEq, Eq, Hash)]
+struct SSnapshotCacheKey {
+    an_hand: [u64; EPlayerIndex::SIZE], // bit sets of remaining cards
+    i_epi_first: usize, // current stich is empty
+    atplnn_point_stich: [(isize, usize); EPlayerIndex::SIZE],
+    n_history: usize,
+}
+impl SSnapshotCacheKey {
+    fn new(rules: &dyn TRules, stichseq: &SStichSequence, ahand: &EnumMap<EPlayerIndex, SHand>, rulestatecache: &SRuleStateCache) -> Self {
+        assert!(stichseq.current_stich().is_empty());
+        Self {
+            an_hand: ahand.map(|hand|
+                hand.cards().iter().fold(0, |n_bits, card| n_bits | (1 << card.to_usize()))
+            ).into_raw(),
+            i_epi_first: stichseq.current_stich().first_playerindex().to_usize(),
+            atplnn_point_stich: rulestatecache.changing.mapepipointstichcount.map(|pointstichcount|
+                (pointstichcount.n_point, pointstichcount.n_stich)
+            ).into_raw(),
+            n_history: rules.snapshot_cache_history(stichseq),
+        }
+    }
+}
+
+// Two cards of epi_current are equivalent if they are worth the same points and no card
+// of another player (in hand or in current stich) lies between them, i.e. they can be exchanged
+// without changing the outcome of any stich.
+fn equivalent_cards(
+    rules: &dyn TRules,
+    stichseq: &SStichSequence,
+    ahand: &EnumMap<EPlayerIndex, SHand>,
+    epi_current: EPlayerIndex,
+    card_lhs: SCard,
+    card_rhs: SCard,
+) -> bool {
+    rules.trumpforfarbe(card_lhs)==rules.trumpforfarbe(card_rhs)
+        && card_points::points_card(card_lhs)==card_points::points_card(card_rhs)
+        && !EPlayerIndex::values()
+            .filter(|epi| *epi!=epi_current)
+            .flat_map(|epi| ahand[epi].cards().iter())
+            .chain(stichseq.current_stich().iter().map(|(_epi, card)| card))
+            .any(|card_other| {
+                let oord = rules.compare_cards(card_lhs, *card_other);
+                matches!(oord, Some(Ordering::Less | Ordering::Greater))
+                    && oord==rules.compare_cards(*card_other, card_rhs)
+            })
+}
+
 fn explore_snapshots_internal<ForEachSnapshot>(
     ahand: &mut EnumMap<EPlayerIndex, SHand>,
     rules: &dyn TRules,
     rulestatecache: &mut SRuleStateCache,
     stichseq: &mut SStichSequence,
     func_filter_allowed_cards: &impl Fn(&SStichSequence, &mut SHandVector),
+    oairulespecific: Option<&dyn TRuleSpecificAI>,
     foreachsnapshot: &ForEachSnapshot,
     snapshotvisualizer: &mut impl TSnapshotVisualizer<ForEachSnapshot::Output>,
+    mapsnapshotcacheoutput: &mut HashMap<(SSnapshotCacheKey, ForEachSnapshot::Bounds), ForEachSnapshot::Output>,
+    bounds: ForEachSnapshot::Bounds,
 ) -> ForEachSnapshot::Output 
     where
         ForEachSnapshot: TForEachSnapshot,
+        ForEachSnapshot::Output: Clone,
 {
-    snapshotvisualizer.begin_snapshot(stichseq, ahand);
     let epi_current = unwrap!(stichseq.current_stich().current_playerindex());
-    let output = if debug_verify_eq!(
+    let b_last_stich = debug_verify_eq!(
         ahand[epi_current].cards().len() <= 1,
         ahand.iter().all(|hand| hand.cards().len() <= 1)
-    ) {
+    );
+    // snapshots reached via different card orders need to be explored only once
+    // (caching only at the beginning of a stich suffices and keeps the cache small)
+    let osnapshotcachekey = if_then_some!(!b_last_stich && stichseq.current_stich().is_empty(),
+        (SSnapshotCacheKey::new(rules, stichseq, ahand, rulestatecache), bounds.clone())
+    );
+    if let Some(output) = osnapshotcachekey.as_ref().and_then(|snapshotcachekey| mapsnapshotcacheoutput.get(snapshotcachekey)) {
+        return output.clone();
+    }
+    snapshotvisualizer.begin_snapshot(stichseq, ahand);
+    let output = if b_last_stich {
         macro_rules! for_each_allowed_card{
             (($i_offset_0: expr, $($i_offset: expr,)*), $stichseq: expr) => {{
                 let epi = epi_current.wrapping_add($i_offset_0);
@@ -225,24 +296,37 @@ fn explore_snapshots_internal<ForEachSnapshot>(
                 output
             }};
         }
-        match stichseq.current_stich().size() {
-            0 => for_each_allowed_card!((0, 1, 2, 3,), stichseq),
-            1 => for_each_allowed_card!((0, 1, 2,), stichseq),
+        match EPlayerIndex::SIZE - stichseq.current_stich().size() { // number of cards missing in stich
+            4 => for_each_allowed_card!((0, 1, 2, 3,), stichseq),
+            3 => for_each_allowed_card!((0, 1, 2,), stichseq),
             2 => for_each_allowed_card!((0, 1,), stichseq),
-            3 => for_each_allowed_card!((0,), stichseq),
-            n_stich_size => {
-                assert_eq!(n_stich_size, 4);
+            1 => for_each_allowed_card!((0,), stichseq),
+            n_cards_missing => {
+                assert_eq!(n_cards_missing, 0);
                 for_each_allowed_card!((), stichseq)
             },
         }
     } else {
-        foreachsnapshot.pruned_output(stichseq, ahand, rulestatecache).unwrap_or_else(|| {
+        foreachsnapshot.pruned_output(&bounds, stichseq, ahand, rulestatecache).unwrap_or_else(|| {
             let mut veccard_allowed = rules.all_allowed_cards(stichseq, &ahand[epi_current]);
             func_filter_allowed_cards(stichseq, &mut veccard_allowed);
-            // TODO? use equivalent card optimization
+            let mut veccard_allowed = veccard_allowed.into_iter().fold(SHandVector::new(), mutate_return!(|veccard_representative, card| {
+                if !veccard_representative.iter().any(|card_representative|
+                    equivalent_cards(rules, stichseq, ahand, epi_current, *card_representative, card)
+                ) {
+                    veccard_representative.push(card);
+                }
+            }));
+            if let Some(airulespecific) = oairulespecific.filter(|_| 1<veccard_allowed.len()) {
+                airulespecific.order_cards(epi_current, stichseq, ahand, &mut veccard_allowed);
+            }
+            let oknownparties = if_then_some!(ForEachSnapshot::B_KNOWN_PARTIES, rules.known_parties(stichseq));
             foreachsnapshot.combine_outputs(
                 epi_current,
-                veccard_allowed.into_iter().map(|card| {
+                oknownparties.as_ref(),
+                &bounds,
+                veccard_allowed,
+                |card, bounds_card| {
                     ahand[epi_current].play_card(card);
                     let output = stichseq.zugeben_and_restore(card, rules, |stichseq| {
                         macro_rules! next_step {() => {explore_snapshots_internal(
@@ -251,8 +335,11 @@ fn explore_snapshots_internal<ForEachSnapshot>(
                             rulestatecache,
                             stichseq,
                             func_filter_allowed_cards,
+                            oairulespecific,
                             foreachsnapshot,
                             snapshotvisualizer,
+                            mapsnapshotcacheoutput,
+                            bounds_card,
                         )}}
                         if stichseq.current_stich().is_empty() {
                             let unregisterstich = rulestatecache.register_stich(
@@ -267,12 +354,15 @@ fn explore_snapshots_internal<ForEachSnapshot>(
                         }
                     });
                     ahand[epi_current].add_card(card);
-                    (card, output)
-                })
+                    output
+                },
             )
         })
     };
     snapshotvisualizer.end_snapshot(&output);
+    if let Some(snapshotcachekey) = osnapshotcachekey {
+        mapsnapshotcacheoutput.insert(snapshotcachekey, output.clone());
+    }
     output
 }
 
@@ -295,7 +385,7 @@ impl<'rules, Pruner> SMinReachablePayoutBase<'rules, Pruner> {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct SPerMinMaxStrategy<T> {
     pub t_min: T,
     pub t_selfish_min: T,
@@ -318,75 +408,86 @@ impl SMinMax {
 
 impl<Pruner: TPruner> TForEachSnapshot for SMinReachablePayoutBase<'_, Pruner> {
     type Output = SMinMax;
+    type Bounds = ();
 
     fn final_output(&self, slcstich: SStichSequenceGameFinished, rulestatecache: &SRuleStateCache) -> Self::Output {
         SMinMax::new_final(self.rules.payout_with_cache(slcstich, self.tpln_stoss_doubling, self.n_stock, rulestatecache))
     }
 
-    fn pruned_output(&self, stichseq: &SStichSequence, ahand: &EnumMap<EPlayerIndex, SHand>, rulestatecache: &SRuleStateCache) -> Option<Self::Output> {
+    fn pruned_output(&self, _bounds: &Self::Bounds, stichseq: &SStichSequence, ahand: &EnumMap<EPlayerIndex, SHand>, rulestatecache: &SRuleStateCache) -> Option<Self::Output> {
         Pruner::pruned_output(self, stichseq, ahand, rulestatecache)
     }
 
-    fn combine_outputs<ItTplCardOutput: Iterator<Item=(SCard, Self::Output)>>(
+    fn combine_outputs<FnExploreCard: FnMut(SCard, Self::Bounds)->Self::Output>(
         &self,
         epi_card: EPlayerIndex,
-        ittplcardoutput: ItTplCardOutput,
+        _oknownparties: Option<&SKnownParties>,
+        _bounds: &Self::Bounds,
+        veccard_allowed: SHandVector,
+        mut fn_explore_card: FnExploreCard,
     ) -> Self::Output {
-        let itminmax = ittplcardoutput.map(|(_card, minmax)| minmax);
-        unwrap!(if self.epi==epi_card {
-            itminmax.reduce(mutate_return!(|minmax_acc, minmax| {
-                // self.epi can always play as good as possible
-                let play_best = |an_payout_acc, an_payout_new: &EnumMap<EPlayerIndex, isize>| {
-                    assign_max_by_key(
-                        an_payout_acc,
-                        an_payout_new.explicit_clone(),
-                        |an_payout| an_payout[self.epi],
-                    );
-                };
-                play_best(&mut minmax_acc.t_min, &minmax.t_min);
-                play_best(&mut minmax_acc.t_selfish_min, &minmax.t_selfish_min);
-                play_best(&mut minmax_acc.t_selfish_max, &minmax.t_selfish_max);
-                play_best(&mut minmax_acc.t_max, &minmax.t_max);
-            }))
-        } else {
-            // other players may play inconveniently for epi_stich
-            itminmax.reduce(mutate_return!(|minmax_acc, minmax| {
-                assign_min_by_key(
-                    &mut minmax_acc.t_min,
-                    minmax.t_min.explicit_clone(),
-                    |an_payout| an_payout[self.epi],
-                );
-                assign_better(
-                    &mut minmax_acc.t_selfish_min,
-                    minmax.t_selfish_min.explicit_clone(),
-                    |an_payout_lhs, an_payout_rhs| {
-                        match an_payout_lhs[epi_card].cmp(&an_payout_rhs[epi_card]) {
-                            Ordering::Less => false,
-                            Ordering::Equal => an_payout_lhs[self.epi] < an_payout_rhs[self.epi],
-                            Ordering::Greater => true,
-                        }
-                    },
-                );
-                assign_better(
-                    &mut minmax_acc.t_selfish_max,
-                    minmax.t_selfish_max.explicit_clone(),
-                    |an_payout_lhs, an_payout_rhs| {
-                        match an_payout_lhs[epi_card].cmp(&an_payout_rhs[epi_card]) {
-                            Ordering::Less => false,
-                            Ordering::Equal => an_payout_lhs[self.epi] > an_payout_rhs[self.epi],
-                            Ordering::Greater => true,
-                        }
-                    },
-                );
+        combine_minmax(
+            self.epi,
+            epi_card,
+            veccard_allowed.into_iter().map(|card| fn_explore_card(card, ())),
+        )
+    }
+}
+
+fn combine_minmax(epi: EPlayerIndex, epi_card: EPlayerIndex, itminmax: impl Iterator<Item=SMinMax>) -> SMinMax {
+    unwrap!(if epi==epi_card {
+        itminmax.reduce(mutate_return!(|minmax_acc, minmax| {
+            // epi can always play as good as possible
+            let play_best = |an_payout_acc, an_payout_new: &EnumMap<EPlayerIndex, isize>| {
                 assign_max_by_key(
-                    &mut minmax_acc.t_max,
-                    minmax.t_max.explicit_clone(),
-                    |an_payout| an_payout[self.epi],
+                    an_payout_acc,
+                    an_payout_new.explicit_clone(),
+                    |an_payout| an_payout[epi],
                 );
+            };
+            play_best(&mut minmax_acc.t_min, &minmax.t_min);
+            play_best(&mut minmax_acc.t_selfish_min, &minmax.t_selfish_min);
+            play_best(&mut minmax_acc.t_selfish_max, &minmax.t_selfish_max);
+            play_best(&mut minmax_acc.t_max, &minmax.t_max);
+        }))
+    } else {
+        // other players may play inconveniently for epi_stich
+        itminmax.reduce(mutate_return!(|minmax_acc, minmax| {
+            assign_min_by_key(
+                &mut minmax_acc.t_min,
+                minmax.t_min.explicit_clone(),
+                |an_payout| an_payout[epi],
+            );
+            assign_better(
+                &mut minmax_acc.t_selfish_min,
+                minmax.t_selfish_min.explicit_clone(),
+                |an_payout_lhs, an_payout_rhs| {
+                    match an_payout_lhs[epi_card].cmp(&an_payout_rhs[epi_card]) {
+                        Ordering::Less => false,
+                        Ordering::Equal => an_payout_lhs[epi] < an_payout_rhs[epi],
+                        Ordering::Greater => true,
+                    }
+                },
+            );
+            assign_better(
+                &mut minmax_acc.t_selfish_max,
+                minmax.t_selfish_max.explicit_clone(),
+                |an_payout_lhs, an_payout_rhs| {
+                    match an_payout_lhs[epi_card].cmp(&an_payout_rhs[epi_card]) {
+                        Ordering::Less => false,
+                        Ordering::Equal => an_payout_lhs[epi] > an_payout_rhs[epi],
+                        Ordering::Greater => true,
+                    }
+                },
+            );
+            assign_max_by_key(
+                &mut minmax_acc.t_max,
+                minmax.t_max.explicit_clone(),
+                |an_payout| an_payout[epi],
+            );
 
-            }))
-        })
-    }
+        }))
+    })
 }
 
 pub type SMinReachablePayout<'rules> = SMinReachablePayoutBase<'rules, SPrunerNothing>;
@@ -420,3 +521,166 @@ impl TPruner for SPrunerViaHint {
         )
     }
 }
+
+// window (alpha, beta) for the payout of SMinReachablePayoutAlphaBeta::epi
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SAlphaBeta {
+    n_alpha: isize,
+    n_beta: isize,
+}
+impl Default for SAlphaBeta {
+    fn default() -> Self {
+        Self {
+            n_alpha: isize::MIN,
+            n_beta: isize::MAX,
+        }
+    }
+}
+
+// decides which players try to maximize the payout of SMinReachablePayoutBase::epi
+pub trait TPlayerModel {
+    const B_KNOWN_PARTIES: bool;
+    fn plays_for_epi(epi: EPlayerIndex, epi_card: EPlayerIndex, oknownparties: Option<&SKnownParties>) -> bool;
+}
+
+// all other players may play inconveniently for epi (i.e. t_min)
+pub struct SPlayerModelAdversarial;
+impl TPlayerModel for SPlayerModelAdversarial {
+    const B_KNOWN_PARTIES: bool = false;
+    fn plays_for_epi(epi: EPlayerIndex, epi_card: EPlayerIndex, _oknownparties: Option<&SKnownParties>) -> bool {
+        epi==epi_card
+    }
+}
+
+// partners known to everybody cooperate with epi, all other players play inconveniently for epi
+pub struct SPlayerModelParties;
+impl TPlayerModel for SPlayerModelParties {
+    const B_KNOWN_PARTIES: bool = true;
+    fn plays_for_epi(epi: EPlayerIndex, epi_card: EPlayerIndex, oknownparties: Option<&SKnownParties>) -> bool {
+        unwrap!(oknownparties).same_party(epi, epi_card)
+    }
+}
+
+// Computes only t_min (using alpha-beta pruning) w.r.t. PlayerModel; remaining strategies are set to t_min.
+// Within t_min, only the payout of epi is exact.
+pub struct SPrunerAlphaBeta<PlayerModel>(std::marker::PhantomData<PlayerModel>);
+pub type SMinReachablePayoutAlphaBeta<'rules> = SMinReachablePayoutBase<'rules, SPrunerAlphaBeta<SPlayerModelAdversarial>>;
+pub type SPartyAwarePayout<'rules> = SMinReachablePayoutBase<'rules, SPrunerAlphaBeta<SPlayerModelParties>>;
+
+impl<PlayerModel: TPlayerModel> TForEachSnapshot for SMinReachablePayoutBase<'_, SPrunerAlphaBeta<PlayerModel>> {
+    type Output = SMinMax;
+    type Bounds = SAlphaBeta;
+    const B_KNOWN_PARTIES: bool = PlayerModel::B_KNOWN_PARTIES;
+
+    fn final_output(&self, slcstich: SStichSequenceGameFinished, rulestatecache: &SRuleStateCache) -> Self::Output {
+        SMinMax::new_final(self.rules.payout_with_cache(slcstich, self.tpln_stoss_doubling, self.n_stock, rulestatecache))
+    }
+
+    fn pruned_output(&self, alphabeta: &Self::Bounds, stichseq: &SStichSequence, ahand: &EnumMap<EPlayerIndex, SHand>, rulestatecache: &SRuleStateCache) -> Option<Self::Output> {
+        let payouthint = &self.rules.payouthints(stichseq, ahand, rulestatecache)[self.epi];
+        let payout_bound = |opayoutinfo: &Option<SPayoutInfo>| {
+            opayoutinfo.as_ref()
+                .map(|payoutinfo| payoutinfo.payout_including_stock(self.n_stock, self.tpln_stoss_doubling))
+        };
+        // if payout is known to lie outside the window, the exact value is irrelevant
+        payout_bound(payouthint.lower_bound()).filter(|n_payout| alphabeta.n_beta<=*n_payout)
+            .or_else(|| payout_bound(payouthint.upper_bound()).filter(|n_payout| *n_payout<=alphabeta.n_alpha))
+            .map(|n_payout| SMinMax::new_final(EPlayerIndex::map_from_fn(|epi|
+                if epi==self.epi {n_payout} else {0}
+            )))
+    }
+
+    fn combine_outputs<FnExploreCard: FnMut(SCard, Self::Bounds)->Self::Output>(
+        &self,
+        epi_card: EPlayerIndex,
+        oknownparties: Option<&SKnownParties>,
+        alphabeta: &Self::Bounds,
+        veccard_allowed: SHandVector,
+        mut fn_explore_card: FnExploreCard,
+    ) -> Self::Output {
+        let mut alphabeta = alphabeta.clone();
+        let mut oan_payout : Option<EnumMap<EPlayerIndex, isize>> = None;
+        let b_plays_for_epi = PlayerModel::plays_for_epi(self.epi, epi_card, oknownparties);
+        for card in veccard_allowed {
+            let an_payout_card = fn_explore_card(card, alphabeta.clone()).t_min;
+            let n_payout_card = an_payout_card[self.epi];
+            if b_plays_for_epi {
+                // self.epi (and possibly his partner) can always play as good as possible
+                assign_max(&mut alphabeta.n_alpha, n_payout_card);
+                match oan_payout {
+                    None => oan_payout = Some(an_payout_card),
+                    Some(ref mut an_payout) => assign_max_by_key(an_payout, an_payout_card, |an_payout| an_payout[self.epi]),
+                }
+            } else {
+                // other players may play inconveniently for epi_stich
+                assign_min(&mut alphabeta.n_beta, n_payout_card);
+                match oan_payout {
+                    None => oan_payout = Some(an_payout_card),
+                    Some(ref mut an_payout) => assign_min_by_key(an_payout, an_payout_card, |an_payout| an_payout[self.epi]),
+                }
+            }
+            if alphabeta.n_beta<=alphabeta.n_alpha {
+                break; // remaining cards cannot influence the result
+            }
+        }
+        SMinMax::new_final(unwrap!(oan_payout))
+    }
+}
+
+// what SMaxReachablePointStich computes for each player's party instead of the payout
+#[derive(Clone, Copy, Debug)]
+pub enum VPointStichObjective {
+    Points,
+    Stichs,
+    PointsAtLeast(isize), // 1 if party reaches the given points (e.g. 31: schneider frei, 91: schneider), 0 otherwise
+}
+
+// Explores like SMinReachablePayout, but rates finished games by points/stichs of each player's party.
+#[derive(Clone, new)]
+pub struct SMaxReachablePointStich<'rules> {
+    rules: &'rules dyn TRules,
+    epi: EPlayerIndex,
+    pointstichobjective: VPointStichObjective,
+}
+
+impl TForEachSnapshot for SMaxReachablePointStich<'_> {
+    type Output = SMinMax;
+    type Bounds = ();
+
+    fn final_output(&self, slcstich: SStichSequenceGameFinished, rulestatecache: &SRuleStateCache) -> Self::Output {
+        let knownparties = self.rules.known_parties(slcstich.get());
+        SMinMax::new_final(EPlayerIndex::map_from_fn(|epi| {
+            let (n_point, n_stich) = EPlayerIndex::values()
+                .filter(|epi_other| knownparties.same_party(epi, *epi_other))
+                .map(|epi_other| &rulestatecache.changing.mapepipointstichcount[epi_other])
+                .fold((0, 0), |(n_point, n_stich), pointstichcount| (
+                    n_point + pointstichcount.n_point,
+                    n_stich + pointstichcount.n_stich,
+                ));
+            match self.pointstichobjective {
+                VPointStichObjective::Points => n_point,
+                VPointStichObjective::Stichs => n_stich.as_num::<isize>(),
+                VPointStichObjective::PointsAtLeast(n_point_threshold) => if n_point_threshold<=n_point {1} else {0},
+            }
+        }))
+    }
+
+    fn pruned_output(&self, _bounds: &Self::Bounds, _stichseq: &SStichSequence, _ahand: &EnumMap<EPlayerIndex, SHand>, _rulestatecache: &SRuleStateCache) -> Option<Self::Output> {
+        None
+    }
+
+    fn combine_outputs<FnExploreCard: FnMut(SCard, Self::Bounds)->Self::Output>(
+        &self,
+        epi_card: EPlayerIndex,
+        _oknownparties: Option<&SKnownParties>,
+        _bounds: &Self::Bounds,
+        veccard_allowed: SHandVector,
+        mut fn_explore_card: FnExploreCard,
+    ) -> Self::Output {
+        combine_minmax(
+            self.epi,
+            epi_card,
+            veccard_allowed.into_iter().map(|card| fn_explore_card(card, ())),
+        )
+    }
+}
@@ -1,11 +1,64 @@
 use crate::ai::{suspicion::*, *};
+#[cfg(not(feature="dreier"))]
 use crate::game;
 use crate::player::{playerrandom::SPlayerRandom, TPlayer};
 use crate::primitives::*;
 use crate::rules::{ruleset::*, *};
 use crate::util::*;
 
+// Rufspiel requires four players, so three-player tests play the remaining games
+fn ruleset_from_string(str_ruleset: &str) -> SRuleSet {
+    #[cfg(feature="dreier")]
+    let str_ruleset = &str_ruleset.replace("[rufspiel]", "");
+    unwrap!(SRuleSet::from_string(str_ruleset))
+}
+
+fn str_game(game: &SGame) -> String {
+    format!("rules: {}\nahand: {}\nvecstich:{}",
+        game.rules,
+        game.ahand.iter().join(", "),
+        game.stichseq.visible_stichs().iter().join(", "),
+    )
+}
+
+// plays random games and calls fn_check whenever the player to play holds at most n_cards_remaining_max cards
+fn check_random_positions(
+    n_games: usize,
+    n_cards_remaining_max: usize,
+    fn_check: impl Fn(&SGame, EPlayerIndex) + Clone + 'static,
+) {
+    crate::game::run::run_simple_game_loop(
+        EPlayerIndex::map_from_fn(|_epi| {
+            let fn_check = fn_check.clone();
+            Box::new(SPlayerRandom::new(
+                /*fn_check_ask_for_card*/move |game: &SGame| {
+                    let epi = unwrap!(game.current_playable_stich().current_playerindex());
+                    if remaining_cards_per_hand(&game.stichseq)[epi] <= n_cards_remaining_max {
+                        fn_check(game, epi);
+                    }
+                },
+            )) as Box<dyn TPlayer>
+        }),
+        n_games,
+        ruleset_from_string(
+            r"
+            base-price=10
+            solo-price=50
+            lauf-min=3
+            [rufspiel]
+            [solo]
+            [wenz]
+            lauf-min=2
+            [bettel]
+            [ramsch]
+            price=20
+            ",
+        )
+    );
+}
+
 #[test]
+#[cfg(not(feature="dreier"))]
 fn test_determine_best_card() {
     // https://www.sauspiel.de/spiele/785105783
     use crate::card::card_values::*;
@@ -19,6 +72,8 @@ fn test_determine_best_card() {
         game::SDoublings::new(SStaticEPI0{}),
         Some(SStossParams::new(
             /*n_stoss_max*/4,
+            EStossTime::BeforeFirstCard,
+            /*b_announce_schneider_schwarz*/false,
         )),
         <dyn TRules>::box_clone(&rulesrufspiel::SRulesRufspiel::new(EPlayerIndex::EPI0, EFarbe::Eichel, payoutdecider::SPayoutDeciderParams::new(
             /*n_payout_base*/100,
@@ -66,14 +121,14 @@ fn test_determine_best_card() {
     // (HO ensures at least that no other player can take away rufsau.)
     // TODO examine optimal solution to this case.
     #[cfg(not(debug_assertions))] {
-        assert_eq!(aisimulating.suggest_card(&game, /*opath_out_dir*/None), HO);
+        assert_eq!(aisimulating.suggest_card(&game, |_,_,_| SNoVisualization), HO);
     }
     play_stichs(&mut game, &[
         (EPlayerIndex::EPI0, [HO, E7, HU, GK]),
     ]);
     #[cfg(not(debug_assertions))] {
-        assert_eq!(aicheating.suggest_card(&game, /*opath_out_dir*/None), E8);
-        assert_eq!(aisimulating.suggest_card(&game, /*opath_out_dir*/None), E8);
+        assert_eq!(aicheating.suggest_card(&game, |_,_,_| SNoVisualization), E8);
+        assert_eq!(aisimulating.suggest_card(&game, |_,_,_| SNoVisualization), E8);
     }
     play_stichs(&mut game, &[
         (EPlayerIndex::EPI0, [SZ, EK, G7, SA]),
@@ -93,6 +148,7 @@ fn detect_expensive_all_possible_hands() {
                         &game.stichseq,
                         game.ahand[epi_fixed].clone(),
                         epi_fixed,
+                        game.open_hand(),
                         game.rules.as_ref()
                     )
                         .collect::<Vec<_>>();
@@ -110,18 +166,22 @@ fn detect_expensive_all_possible_hands() {
                         struct SLeafCounter;
                         impl TForEachSnapshot for SLeafCounter {
                             type Output = usize;
+                            type Bounds = ();
                             fn final_output(&self, _slcstich: SStichSequenceGameFinished, _rulestatecache: &SRuleStateCache) -> Self::Output {
                                 1 // leaf
                             }
-                            fn pruned_output(&self, _stichseq: &SStichSequence, _ahand: &EnumMap<EPlayerIndex, SHand>, _rulestatecache: &SRuleStateCache) -> Option<Self::Output> {
+                            fn pruned_output(&self, _bounds: &Self::Bounds, _stichseq: &SStichSequence, _ahand: &EnumMap<EPlayerIndex, SHand>, _rulestatecache: &SRuleStateCache) -> Option<Self::Output> {
                                 None
                             }
-                            fn combine_outputs<ItTplCardOutput: Iterator<Item=(SCard, Self::Output)>>(
+                            fn combine_outputs<FnExploreCard: FnMut(SCard, Self::Bounds)->Self::Output>(
                                 &self,
                                 _epi_card: EPlayerIndex,
-                                ittplcardoutput: ItTplCardOutput,
+                                _oknownparties: Option<&SKnownParties>,
+                                _bounds: &Self::Bounds,
+                                veccard_allowed: SHandVector,
+                                mut fn_explore_card: FnExploreCard,
                             ) -> Self::Output {
-                                ittplcardoutput.map(|tplcardoutput| tplcardoutput.1).sum()
+                                veccard_allowed.into_iter().map(|card| fn_explore_card(card, ())).sum()
                             }
                         }
                         assert_bound(
@@ -140,7 +200,7 @@ fn detect_expensive_all_possible_hands() {
             },
         )) as Box<dyn TPlayer>),
         /*n_games*/4,
-        unwrap!(SRuleSet::from_string(
+        ruleset_from_string(
             r"
             base-price=10
             solo-price=50
@@ -152,10 +212,149 @@ fn detect_expensive_all_possible_hands() {
             [stoss]
             max=3
             ",
-        ))
+        )
+    );
+}
+
+fn payout_t_min(game: &SGame, epi: EPlayerIndex, foreachsnapshot: &impl TForEachSnapshot<Output=SMinMax>) -> isize {
+    explore_snapshots(
+        &mut game.ahand.clone(),
+        game.rules.as_ref(),
+        &mut game.stichseq.clone(),
+        &|_vecstich_complete, _vecstich_successor| {/*no filtering*/},
+        foreachsnapshot,
+        &mut SNoVisualization,
+    ).t_min[epi]
+}
+
+#[test]
+fn test_alphabeta_agrees_with_minmax() {
+    check_random_positions(/*n_games*/20, /*n_cards_remaining_max*/3, |game, epi| {
+        assert_eq!(
+            payout_t_min(game, epi, &SMinReachablePayoutAlphaBeta::new_from_game(game)),
+            payout_t_min(game, epi, &SMinReachablePayout::new_from_game(game)),
+            "{}",
+            str_game(game),
+        );
+    });
+}
+
+#[test]
+fn test_party_aware_payout() {
+    check_random_positions(/*n_games*/20, /*n_cards_remaining_max*/3, |game, epi| {
+        let n_payout_min = payout_t_min(game, epi, &SMinReachablePayoutAlphaBeta::new_from_game(game));
+        let n_payout_parties = payout_t_min(game, epi, &SPartyAwarePayout::new_from_game(game));
+        // cooperating partners can only improve the result
+        assert!(n_payout_min <= n_payout_parties, "{}", str_game(game));
+        if game.rules.playerindex().is_none() {
+            // Ramsch: nobody cooperates
+            assert_eq!(n_payout_min, n_payout_parties, "{}", str_game(game));
+        }
+    });
+}
+
+#[test]
+fn test_point_stich_objective() {
+    check_random_positions(/*n_games*/10, /*n_cards_remaining_max*/3, |game, epi| {
+        let minmax = |pointstichobjective| explore_snapshots(
+            &mut game.ahand.clone(),
+            game.rules.as_ref(),
+            &mut game.stichseq.clone(),
+            &|_vecstich_complete, _vecstich_successor| {/*no filtering*/},
+            &SMaxReachablePointStich::new(game.rules.as_ref(), epi, pointstichobjective),
+            &mut SNoVisualization,
+        );
+        let str_game = str_game(game);
+        let minmax_points = minmax(VPointStichObjective::Points);
+        assert!(minmax_points.t_min[epi] <= minmax_points.t_max[epi], "{}", str_game);
+        assert!((0..=120).contains(&minmax_points.t_min[epi]), "{}", str_game);
+        let n_stich_max = minmax(VPointStichObjective::Stichs).t_max[epi];
+        assert!((0..=game.kurzlang().cards_per_player().as_num::<isize>()).contains(&n_stich_max), "{}", str_game);
+        // reaching a threshold is guaranteed iff the guaranteed points reach it
+        let n_point_min = minmax_points.t_min[epi];
+        assert_eq!(minmax(VPointStichObjective::PointsAtLeast(n_point_min)).t_min[epi], 1, "{}", str_game);
+        assert_eq!(minmax(VPointStichObjective::PointsAtLeast(n_point_min+1)).t_min[epi], 0, "{}", str_game);
+    });
+}
+
+#[test]
+fn test_ismcts_suggests_allowed_card() {
+    check_random_positions(/*n_games*/5, /*n_cards_remaining_max*/usize::MAX, |game, _epi| {
+        let card = SAi::new_ismcts(
+            /*n_rank_rules_samples*/1,
+            /*n_iterations*/200,
+            /*duration*/std::time::Duration::from_secs(1),
+        ).suggest_card(game, |_,_,_| SNoVisualization);
+        assert!(SDetermineBestCard::new_from_game(game).veccard_allowed.contains(&card), "{}", str_game(game));
+    });
+}
+
+#[test]
+#[cfg(not(feature="dreier"))]
+fn test_ismcts_agrees_with_cheating() {
+    use crate::card::card_values::*;
+    let game = game_keep_trumpf_for_last_stich();
+    let card_cheating = SAi::new_cheating(/*n_rank_rules_samples*/1, /*n_suggest_card_branches*/2)
+        .suggest_card(&game, |_,_,_| SNoVisualization);
+    assert_eq!(card_cheating, S8);
+    // keeping HO is at least as good for every distribution of the unknown cards
+    assert_eq!(
+        SAi::new_ismcts(
+            /*n_rank_rules_samples*/1,
+            /*n_iterations*/1000,
+            /*duration*/std::time::Duration::from_secs(10),
+        ).suggest_card(&game, |_,_,_| SNoVisualization),
+        card_cheating,
     );
 }
 
+#[test]
+fn test_anytime_respects_deadline() {
+    use std::time::{Duration, Instant};
+    // small positions suffice since the deadline logic does not depend on the size of the search tree
+    check_random_positions(/*n_games*/4, /*n_cards_remaining_max*/3, |game, epi| {
+        let determinebestcard = SDetermineBestCard::new_from_game(game);
+        if determinebestcard.veccard_allowed.len() <= 1 {
+            return; // no search needed (as in SAi::suggest_card)
+        }
+        let n_cards_hand = determinebestcard.hand_fixed.cards().len();
+        let n_hands_per_round = rayon::current_num_threads();
+        let itahand = || forever_rand_hands(&game.stichseq, game.ahand[epi].clone(), epi, game.open_hand(), game.rules.as_ref());
+        let anytime = |itahand, instant_deadline, fn_now: &(dyn Fn()->Instant + Sync)| {
+            let determinebestcardanytimeresult = determine_best_card_anytime(
+                &determinebestcard,
+                itahand,
+                &SMinReachablePayoutLowerBoundViaHint::new(game.rules.as_ref(), epi, /*tpln_stoss_doubling*/(0, 0), /*n_stock*/0),
+                instant_deadline,
+                fn_now,
+            );
+            let card = *unwrap!(determinebestcardanytimeresult.determinebestcardresult.cards_with_maximum_value().0.first());
+            assert!(determinebestcard.veccard_allowed.contains(&card), "{}", str_game(game));
+            (determinebestcardanytimeresult.n_hands, determinebestcardanytimeresult.n_branches)
+        };
+        let instant_begin = Instant::now();
+        // deadline already passed: only the initial round
+        assert_eq!(
+            anytime(Box::new(itahand()) as Box<dyn Iterator<Item=_> + Send>, instant_begin, &|| instant_begin + Duration::from_secs(1)),
+            (n_hands_per_round, 1),
+            "{}", str_game(game),
+        );
+        // deadline never reached: deepen in each round until hands are exhausted
+        assert_eq!(
+            anytime(Box::new(itahand().take(3 * n_hands_per_round)), instant_begin + Duration::from_secs(1), &|| instant_begin),
+            (3 * n_hands_per_round, n_cards_hand.min(3)),
+            "{}", str_game(game),
+        );
+        // clock advancing with each query: infinitely many hands, but search stops at the deadline
+        let n_millis = std::sync::atomic::AtomicU64::new(0);
+        anytime(
+            Box::new(itahand()),
+            instant_begin + Duration::from_millis(1000),
+            &|| instant_begin + Duration::from_millis(n_millis.fetch_add(1, std::sync::atomic::Ordering::Relaxed)),
+        );
+    });
+}
+
 // TODO (Sauspiel 964899954)
 // Rufspiel(EPI2), EPI2 is first
 // 2 EK H7 E7 EA
@@ -166,3 +365,194 @@ fn detect_expensive_all_possible_hands() {
 // 2 SU H9 Hz HU
 // 1 GK G9 GZ GA
 // 0 E8 G8 GU SK
+
+#[test]
+#[cfg(not(feature="dreier"))]
+fn test_suggest_doubling() {
+    use crate::card::card_values::*;
+    let ruleset = unwrap!(SRuleSet::from_string(
+        r"
+        base-price=10
+        solo-price=50
+        lauf-min=3
+        [rufspiel]
+        [solo]
+        [doubling]
+        ",
+    ));
+    let ai = SAi::new_cheating(/*n_rank_rules_samples*/10, /*n_suggest_card_branches*/1)
+        .with_doublingparams(SDoublingParams{
+            n_samples: 5,
+            f_payout_threshold: 20.,
+        });
+    assert!(ai.suggest_doubling(EPlayerIndex::EPI0, &[EO, GO, HO, SO], &ruleset, /*n_stock*/0));
+    assert!(!ai.suggest_doubling(EPlayerIndex::EPI0, &[E7, G7, H7, S7], &ruleset, /*n_stock*/0));
+}
+
+#[test]
+#[cfg(not(feature="dreier"))]
+fn test_suggest_stoss() {
+    use crate::card::card_values::*;
+    use crate::rules::{payoutdecider::*, rulessolo::*, tests::TPayoutDeciderSoloLikeDefault};
+    let new_game = |aacard_hand: [[SCard; 8]; 4]| game::SGame::new(
+        EPlayerIndex::map_from_raw(aacard_hand).map_into(|acard| acard.into()),
+        game::SDoublings::new(SStaticEPI0{}),
+        Some(SStossParams::new(
+            /*n_stoss_max*/4,
+            EStossTime::BeforeFirstCard,
+            /*b_announce_schneider_schwarz*/false,
+        )),
+        TRulesBoxClone::box_clone(sololike(
+            EPlayerIndex::EPI0,
+            EFarbe::Herz,
+            ESoloLike::Solo,
+            SPayoutDeciderPointBased::default_payoutdecider(/*n_payout_base*/50, /*n_payout_schneider_schwarz*/10, SLaufendeParams::new(10, 3)),
+        ).as_ref()),
+        /*n_stock*/0,
+    );
+    let ai = SAi::new_cheating(/*n_rank_rules_samples*/1, /*n_suggest_card_branches*/1)
+        .with_stossdecisionparams(SStossDecisionParams{
+            n_samples: 8,
+            n_branches: 2,
+            f_payout_threshold: 10.,
+        });
+    // opponent holding all Ober and Unter gives Stoss, weak declarer refrains from re-Stoss
+    let mut game = new_game([
+        [HA, HZ, HK, H9, E7, E8, G7, S7],
+        [EO, GO, HO, SO, EU, GU, HU, SU],
+        [H8, E9, EZ, EK, EA, G8, G9, GZ],
+        [H7, GK, GA, S8, S9, SZ, SK, SA],
+    ]);
+    assert!(ai.suggest_stoss(&game, EPlayerIndex::EPI1));
+    unwrap!(game.stoss(EPlayerIndex::EPI1));
+    assert!(!ai.suggest_stoss(&game, EPlayerIndex::EPI0));
+    // declarer holding all Ober and Unter gives re-Stoss
+    let mut game = new_game([
+        [EO, GO, HO, SO, EU, GU, HU, SU],
+        [E7, E8, E9, G7, G8, G9, S7, S8],
+        [HA, HZ, HK, H9, H8, H7, EA, EZ],
+        [EK, GA, GZ, GK, SA, SZ, SK, S9],
+    ]);
+    unwrap!(game.stoss(EPlayerIndex::EPI1));
+    assert!(ai.suggest_stoss(&game, EPlayerIndex::EPI0));
+}
+
+// EPI1 should keep HO to beat declarer's HU in the last stich instead of trumping partner's stich
+#[cfg(not(feature="dreier"))]
+fn game_keep_trumpf_for_last_stich() -> SGame {
+    use crate::card::card_values::*;
+    use crate::rules::{payoutdecider::*, rulessolo::*, tests::TPayoutDeciderSoloLikeDefault};
+    let mut game = game::SGame::new(
+        EPlayerIndex::map_from_raw([
+            [GK, H9, EK, SO, E8, GO, G8, HU],
+            [EU, S9, E9, H8, SK, HK, S8, HO],
+            [GZ, E7, EA, GU, SU, EO, GA, G7],
+            [G9, SZ, EZ, HA, SA, H7, S7, HZ],
+        ]).map_into(|acard| acard.into()),
+        game::SDoublings::new(SStaticEPI0{}),
+        /*ostossparams*/None,
+        TRulesBoxClone::box_clone(sololike(
+            EPlayerIndex::EPI0,
+            EFarbe::Herz,
+            ESoloLike::Solo,
+            SPayoutDeciderPointBased::default_payoutdecider(/*n_payout_base*/50, /*n_payout_schneider_schwarz*/10, SLaufendeParams::new(10, 3)),
+        ).as_ref()),
+        /*n_stock*/0,
+    );
+    for card in [
+        GK, EU, GZ, G9,
+        S9, E7, SZ, H9,
+        EK, E9, EA, EZ,
+        GU, HA, SO, H8,
+        E8, SK, SU, SA,
+        EO, H7, GO, HK,
+        GA, S7, G8,
+    ] {
+        let epi = unwrap!(game.which_player_can_do_something()).0;
+        unwrap!(game.zugeben(card, epi));
+    }
+    game
+}
+
+#[test]
+#[cfg(not(feature="dreier"))]
+fn test_rulespecific_ai() {
+    use crate::card::card_values::*;
+    use crate::rules::{payoutdecider::*, rulesbettel::*, rulesramsch::*, rulessolo::*, tests::TPayoutDeciderSoloLikeDefault};
+    let new_game = |aacard_hand: [[SCard; 8]; 4], rules: &dyn TRules| game::SGame::new(
+        EPlayerIndex::map_from_raw(aacard_hand).map_into(|acard| acard.into()),
+        game::SDoublings::new(SStaticEPI0{}),
+        /*ostossparams*/None,
+        rules.box_clone(),
+        /*n_stock*/0,
+    );
+    let play_cards = |game: &mut SGame, slccard: &[SCard]| {
+        for card in slccard {
+            let epi = unwrap!(game.which_player_can_do_something()).0;
+            unwrap!(game.zugeben(*card, epi));
+        }
+    };
+    let suggest_card = |game: &SGame| game.rules.rulespecific_ai()
+        .and_then(|airulespecific| airulespecific.suggest_card(game));
+    {
+        let rules = sololike(
+            EPlayerIndex::EPI0,
+            EFarbe::Herz,
+            ESoloLike::Solo,
+            SPayoutDeciderPointBased::default_payoutdecider(/*n_payout_base*/50, /*n_payout_schneider_schwarz*/10, SLaufendeParams::new(10, 3)),
+        );
+        let mut game = new_game([
+            [EO, GO, HU, HA, HZ, E7, G7, S7],
+            [EA, E8, G8, G9, S8, S9, H7, SU],
+            [EZ, EK, GA, GZ, SA, SZ, H8, H9],
+            [E9, EU, GU, GK, SK, HO, SO, HK],
+        ], rules.upcast());
+        // declarer draws trumpf
+        assert_eq!(suggest_card(&game), Some(EO));
+        play_cards(&mut game, &[E7, EA]);
+        // opponent schmiert
+        assert_eq!(suggest_card(&game), Some(EZ));
+    }
+    {
+        let game = game_keep_trumpf_for_last_stich();
+        // heuristic and exact search both keep HO to beat declarer's HU in the last stich
+        assert_eq!(suggest_card(&game), Some(S8));
+        assert_eq!(
+            SAi::new_cheating(/*n_rank_rules_samples*/1, /*n_suggest_card_branches*/2)
+                .suggest_card(&game, |_,_,_| SNoVisualization),
+            S8,
+        );
+    }
+    {
+        let rules = SRulesBettel::<SBettelAllAllowedCardsWithinStichNormal>::new(EPlayerIndex::EPI0, /*i_prio*/0, /*n_payout_base*/50, /*b_brett*/false);
+        let mut game = new_game([
+            [E9, G7, G8, S7, S8, H7, H8, H9],
+            [E7, EA, EZ, G9, GZ, GU, GO, GK],
+            [E8, EK, EO, EU, GA, SA, SZ, SK],
+            [S9, SU, SO, HA, HZ, HK, HU, HO],
+        ], &rules);
+        play_cards(&mut game, &[E9]);
+        // opponent leaves the stich to the declarer
+        assert_eq!(suggest_card(&game), Some(E7));
+    }
+    let ramsch_game = |durchmarsch| {
+        let mut game = new_game([
+            [EO, GO, HO, SO, EA, GA, SA, E7],
+            [E8, E9, EZ, EK, G8, G9, GZ, GK],
+            [S8, S9, SZ, SK, G7, S7, H7, H8],
+            [EU, GU, HU, SU, HA, HZ, HK, H9],
+        ], &SRulesRamsch::new(/*n_price*/10, durchmarsch, /*b_jungfrau*/false, /*on_price_stock*/None, /*b_schieben*/false));
+        play_cards(&mut game, &[
+            EO, E8, H7, H9,
+            GO, E9, H8, HK,
+            HO, EZ, S8, HZ,
+            SO, EK, S9, HA,
+            EA, G8, SZ,
+        ]);
+        game
+    };
+    // last player stops Durchmarsch
+    assert!(matches!(suggest_card(&ramsch_game(VDurchmarsch::All)), Some(EU|GU|HU|SU)));
+    // without Durchmarsch, there is no reason to take the stich
+    assert_eq!(suggest_card(&ramsch_game(VDurchmarsch::None)), None);
+}
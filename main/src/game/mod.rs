@@ -117,6 +117,8 @@ pub fn random_hand(n_size: usize, veccard : &mut Vec<SCard>) -> SHandVector {
 pub enum VGamePreparationsFinish {
     DetermineRules(SDetermineRules),
     DirectGame(SGame),
+    DerAlteMuss(SDerAlteMuss),
+    Schieben(SSchieben),
     Stock(SGameResult),
 }
 
@@ -129,6 +131,7 @@ impl TGamePhase for SGamePreparations {
     }
 
     fn finish_success(self) -> Self::Finish {
+        let oepi_alter = self.epi_der_alte_muss();
         let mut vectplepirules : Vec<(_, Box<dyn TActivelyPlayableRules>)> = self.gameannouncements.into_iter()
             .filter_map(|(epi, orules)| orules.map(|rules| (epi, rules)))
             .collect();
@@ -141,16 +144,37 @@ impl TGamePhase for SGamePreparations {
                 self.n_stock,
                 tplepirules_current_bid,
             ))
+        } else if let Some(epi_alter) = oepi_alter {
+            VGamePreparationsFinish::DerAlteMuss(SDerAlteMuss {
+                aveccard: self.aveccard,
+                doublings: self.doublings,
+                ruleset: self.ruleset,
+                n_stock: self.n_stock,
+                epi_alter,
+                orules: None,
+            })
         } else {
             match self.ruleset.stockorramsch {
                 VStockOrT::OrT(ref rulesramsch) => {
-                    VGamePreparationsFinish::DirectGame(SGame::new(
-                        self.aveccard,
-                        self.doublings,
-                        self.ruleset.ostossparams.clone(),
-                        rulesramsch.clone(),
-                        self.n_stock,
-                    ))
+                    if rulesramsch.with_schieben().is_some() {
+                        let rules = rulesramsch.clone();
+                        VGamePreparationsFinish::Schieben(SSchieben {
+                            aveccard: self.aveccard,
+                            doublings: self.doublings,
+                            ruleset: self.ruleset,
+                            n_stock: self.n_stock,
+                            rules,
+                            schieben: SPlayersInRound::new(SStaticEPI0{}),
+                        })
+                    } else {
+                        VGamePreparationsFinish::DirectGame(SGame::new(
+                            self.aveccard,
+                            self.doublings,
+                            self.ruleset.ostossparams.clone(),
+                            rulesramsch.clone(),
+                            self.n_stock,
+                        ))
+                    }
                 },
                 VStockOrT::Stock(n_stock) => {
                     let n_stock = match self.ruleset.oedoublingscope {
@@ -172,7 +196,7 @@ impl TGamePhase for SGamePreparations {
 }
 
 macro_rules! impl_fullhand { () => {
-    pub fn fullhand(&self, epi: EPlayerIndex) -> SFullHand {
+    pub fn fullhand(&self, epi: EPlayerIndex) -> SFullHand<'_> {
         SFullHand::new(&self.aveccard[epi], self.ruleset.ekurzlang)
     }
 }}
@@ -180,6 +204,18 @@ macro_rules! impl_fullhand { () => {
 impl SGamePreparations {
     impl_fullhand!();
 
+    fn epi_der_alte_muss(&self) -> Option<EPlayerIndex> {
+        if_then_some!(self.ruleset.b_der_alte_muss,
+            unwrap!(EPlayerIndex::values().find(|epi|
+                self.aveccard[*epi].contains(&SCard::new(EFarbe::Eichel, ESchlag::Ober))
+            ))
+        )
+            .filter(|epi_alter| // fall back to stock/ramsch if he cannot play anything
+                allowed_rules(&self.ruleset.avecrulegroup[*epi_alter], self.fullhand(*epi_alter))
+                    .any(|orules| orules.is_some())
+            )
+    }
+
     pub fn announce_game(&mut self, epi: EPlayerIndex, orules: Option<Box<dyn TActivelyPlayableRules>>) -> Result<(), Error> {
         if Some(epi)!=self.which_player_can_do_something() {
             bail!("Wrong player index");
@@ -196,6 +232,206 @@ impl SGamePreparations {
     }
 }
 
+#[derive(Debug)]
+pub struct SDerAlteMuss {
+    pub aveccard : EnumMap<EPlayerIndex, SHandVector>,
+    pub doublings : SDoublings,
+    pub ruleset : SRuleSet,
+    pub n_stock : isize,
+    pub epi_alter : EPlayerIndex,
+    orules : Option<Box<dyn TActivelyPlayableRules>>,
+}
+
+impl TGamePhase for SDerAlteMuss {
+    type ActivePlayerInfo = (EPlayerIndex, Vec<SRuleGroup>);
+    type Finish = VHochzeitOrGame;
+
+    fn which_player_can_do_something(&self) -> Option<Self::ActivePlayerInfo> {
+        if_then_some!(self.orules.is_none(), (
+            self.epi_alter,
+            self.ruleset.avecrulegroup[self.epi_alter].iter()
+                .filter_map(SRuleGroup::without_nothing)
+                .collect()
+        ))
+    }
+
+    fn finish_success(self) -> Self::Finish {
+        VHochzeitOrGame::new(
+            self.aveccard,
+            self.doublings,
+            self.ruleset,
+            unwrap!(self.orules).upcast().box_clone(),
+            self.n_stock,
+        )
+    }
+}
+
+impl SDerAlteMuss {
+    impl_fullhand!();
+
+    pub fn announce_game(&mut self, epi: EPlayerIndex, rules: Box<dyn TActivelyPlayableRules>) -> Result<(), Error> {
+        if Some(epi)!=self.which_player_can_do_something().map(|(epi, ref _vecrulegroup)| epi) {
+            bail!("announce_game not allowed for specified EPlayerIndex");
+        }
+        if Some(epi)!=rules.playerindex() {
+            bail!("Only actively playable rules can be announced");
+        }
+        if !rules.can_be_played(self.fullhand(epi)) {
+            bail!("Rules cannot be played. {}", SDisplayCardSlice(&self.aveccard[epi]));
+        }
+        self.orules = Some(rules);
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct SSchieben {
+    pub aveccard : EnumMap<EPlayerIndex, SHandVector>,
+    pub doublings : SDoublings,
+    pub ruleset : SRuleSet,
+    pub n_stock : isize,
+    pub rules : Box<dyn TRules>,
+    pub schieben : SPlayersInRound<bool, SStaticEPI0>,
+}
+
+impl TGamePhase for SSchieben {
+    type ActivePlayerInfo = EPlayerIndex;
+    type Finish = SGame;
+
+    fn which_player_can_do_something(&self) -> Option<Self::ActivePlayerInfo> {
+        self.schieben.current_playerindex()
+    }
+
+    fn finish_success(self) -> Self::Finish {
+        SGame::new(
+            self.aveccard,
+            self.doublings,
+            self.ruleset.ostossparams.clone(),
+            self.rules,
+            self.n_stock,
+        )
+    }
+}
+
+impl SSchieben {
+    impl_fullhand!();
+
+    pub fn announce_schieben(&mut self, epi: EPlayerIndex, b_schieben: bool) -> Result<(), Error> {
+        if Some(epi)!=self.which_player_can_do_something() {
+            bail!("Wrong player index");
+        }
+        if b_schieben {
+            self.rules = unwrap!(self.rules.with_schieben());
+        }
+        self.schieben.push(b_schieben);
+        Ok(())
+    }
+}
+
+#[allow(clippy::large_enum_variant)] // It is ok for Game to be so large
+#[derive(Debug)]
+pub enum VHochzeitOrGame {
+    Hochzeit(SHochzeit),
+    Game(SGame),
+}
+
+impl VHochzeitOrGame {
+    fn new(
+        aveccard: EnumMap<EPlayerIndex, SHandVector>,
+        doublings: SDoublings,
+        ruleset: SRuleSet,
+        rules: Box<dyn TRules>,
+        n_stock: isize,
+    ) -> Self {
+        if rules.playerindex_offering().is_some() {
+            VHochzeitOrGame::Hochzeit(SHochzeit {
+                aveccard,
+                doublings,
+                ruleset,
+                n_stock,
+                rules,
+                n_declined: 0,
+            })
+        } else {
+            VHochzeitOrGame::Game(SGame::new(
+                aveccard,
+                doublings,
+                ruleset.ostossparams.clone(),
+                rules,
+                n_stock,
+            ))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SHochzeit {
+    pub aveccard : EnumMap<EPlayerIndex, SHandVector>,
+    pub doublings : SDoublings,
+    pub ruleset : SRuleSet,
+    pub n_stock : isize,
+    pub rules : Box<dyn TRules>,
+    n_declined : usize,
+}
+
+impl TGamePhase for SHochzeit {
+    type ActivePlayerInfo = EPlayerIndex;
+    type Finish = SGame;
+
+    fn which_player_can_do_something(&self) -> Option<Self::ActivePlayerInfo> {
+        // the other players are asked one after another until somebody accepts the offer
+        self.rules.playerindex_offering()
+            .filter(|_epi_offering| self.n_declined < EPlayerIndex::SIZE-1)
+            .map(|epi_offering| epi_offering.wrapping_add(self.n_declined+1))
+    }
+
+    fn finish_success(self) -> Self::Finish {
+        SGame::new(
+            self.aveccard,
+            self.doublings,
+            self.ruleset.ostossparams.clone(),
+            self.rules,
+            self.n_stock,
+        )
+    }
+}
+
+impl SHochzeit {
+    impl_fullhand!();
+
+    // cards that epi may give back in exchange for the offered trumpf
+    pub fn cards_to_exchange(&self, epi: EPlayerIndex) -> impl Iterator<Item=SCard> + '_ {
+        self.aveccard[epi].iter().copied()
+            .filter(|card| !self.rules.trumpforfarbe(*card).is_trumpf())
+    }
+
+    pub fn accept(&mut self, epi: EPlayerIndex, ocard_exchange: Option<SCard>) -> Result<(), Error> {
+        if Some(epi)!=self.which_player_can_do_something() {
+            bail!("Wrong player index");
+        }
+        if let Some(card_exchange) = ocard_exchange {
+            if !self.cards_to_exchange(epi).any(|card| card==card_exchange) {
+                bail!("{} cannot be given in exchange for the offered trumpf", card_exchange);
+            }
+            let epi_offering = unwrap!(self.rules.playerindex_offering());
+            let card_offered = unwrap!(self.aveccard[epi_offering].iter().copied()
+                .filter(|card| self.rules.trumpforfarbe(*card).is_trumpf())
+                .exactly_one()
+                .ok());
+            for (epi_from, card) in [(epi_offering, card_offered), (epi, card_exchange)] {
+                let i_card = unwrap!(self.aveccard[epi_from].iter().position(|card_hand| *card_hand==card));
+                self.aveccard[epi_from].swap_remove(i_card);
+            }
+            self.aveccard[epi].push(card_offered);
+            self.aveccard[epi_offering].push(card_exchange);
+            self.rules = unwrap!(self.rules.with_offer_accepted(epi));
+        } else {
+            self.n_declined += 1;
+        }
+        Ok(())
+    }
+}
+
 #[derive(new, Debug)]
 pub struct SDetermineRules {
     pub aveccard : EnumMap<EPlayerIndex, SHandVector>,
@@ -208,7 +444,7 @@ pub struct SDetermineRules {
 
 impl TGamePhase for SDetermineRules {
     type ActivePlayerInfo = (EPlayerIndex, Vec<SRuleGroup>);
-    type Finish = SGame;
+    type Finish = VHochzeitOrGame;
 
     /*
         Example:
@@ -242,10 +478,10 @@ impl TGamePhase for SDetermineRules {
     fn finish_success(self) -> Self::Finish {
         assert!(self.vectplepirules_queued.is_empty());
         assert_eq!(self.ruleset.ekurzlang, EKurzLang::from_cards_per_player(self.aveccard[EPlayerIndex::EPI0].len()));
-        SGame::new(
+        VHochzeitOrGame::new(
             self.aveccard,
             self.doublings,
-            self.ruleset.ostossparams.clone(),
+            self.ruleset,
             self.tplepirules_current_bid.1.upcast().box_clone(),
             self.n_stock,
         )
@@ -461,12 +697,18 @@ impl<Ruleset, GameAnnouncements, DetermineRules> TGamePhase for SGameGeneric<Rul
             self.current_playable_stich().current_playerindex().map(|epi_current| (
                 epi_current,
                 if let Some(ref stossparams) = self.ostossparams {
-                    if self.stichseq.no_card_played() // TODORULES Adjustable latest time of stoss
-                        && self.vecstoss.len() < stossparams.n_stoss_max
-                    {
+                    if self.vecstoss.len() < stossparams.n_stoss_max {
                         EPlayerIndex::values()
                             .filter(|epi| {
-                                self.rules.stoss_allowed(*epi, &self.vecstoss, &self.ahand[*epi])
+                                let b_stoss_in_time = match stossparams.estosstime {
+                                    EStossTime::BeforeFirstCard => self.stichseq.no_card_played(),
+                                    EStossTime::BeforeOwnFirstCard => {
+                                        self.stichseq.completed_stichs().is_empty()
+                                            && self.stichseq.current_stich().get(*epi).is_none()
+                                    },
+                                };
+                                b_stoss_in_time
+                                    && self.rules.stoss_allowed(*epi, &self.vecstoss, &self.ahand[*epi])
                             })
                             .collect()
                     } else {
@@ -612,6 +854,11 @@ impl<Ruleset, GameAnnouncements, DetermineRules> SGameGeneric<Ruleset, GameAnnou
         self.stichseq.current_stich()
     }
 
+    pub fn open_hand(&self) -> Option<(EPlayerIndex, SHand)> {
+        self.rules.playerindex_open_hand(&self.stichseq)
+            .map(|epi_open| (epi_open, self.ahand[epi_open].clone()))
+    }
+
     pub fn kurzlang(&self) -> EKurzLang {
         #[cfg(debug_assertions)] {
             let cards_per_player = |epi| {
@@ -641,6 +888,27 @@ impl<Ruleset, GameAnnouncements, DetermineRules> SGameGeneric<Ruleset, GameAnnou
         }
     }
 
+    pub fn schneider_schwarz_announceable(&self, epi: EPlayerIndex) -> Option<ESchneiderSchwarz> {
+        if self.ostossparams.as_ref().is_some_and(|stossparams| stossparams.b_announce_schneider_schwarz)
+            && self.stichseq.no_card_played()
+            && Some(epi)==self.rules.playerindex()
+        {
+            ESchneiderSchwarz::values()
+                .filter(|&eschneiderschwarz| Some(eschneiderschwarz) > self.rules.announced_schneider_schwarz())
+                .find(|&eschneiderschwarz| self.rules.with_announced_schneider_schwarz(eschneiderschwarz).is_some())
+        } else {
+            None
+        }
+    }
+
+    pub fn announce_schneider_schwarz(&mut self, epi: EPlayerIndex, eschneiderschwarz: ESchneiderSchwarz) -> Result<(), Error> {
+        if self.schneider_schwarz_announceable(epi).is_none_or(|eschneiderschwarz_next| eschneiderschwarz < eschneiderschwarz_next) {
+            bail!("Announcing {:?} not allowed for {:?}", eschneiderschwarz, epi);
+        }
+        self.rules = unwrap!(self.rules.with_announced_schneider_schwarz(eschneiderschwarz));
+        Ok(())
+    }
+
     pub fn zugeben(&mut self, card: SCard, epi: EPlayerIndex) -> Result<(), Error> {
         if Some(epi)!=self.which_player_can_do_something().map(|gameaction| gameaction.0) {
             bail!("Wrong player index");
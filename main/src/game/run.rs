@@ -9,30 +9,89 @@ use std::sync::mpsc;
 pub struct SAtTable {
     pub player: Box<dyn TPlayer>,
     pub n_money: isize,
+    pub n_games_doubled: usize,
+    pub n_money_doubled: isize, // money won or lost in games where player doubled
 }
 
-pub fn run_simple_game_loop(aplayer: EnumMap<EPlayerIndex, Box<dyn TPlayer>>, n_games: usize, ruleset: SRuleSet) -> ([SAtTable; 4], isize) {
-    let mut aattable = aplayer.map_into(|player| SAtTable{player, n_money:0});
+fn communicate_via_channel<T: std::fmt::Debug>(f: impl FnOnce(mpsc::Sender<T>)) -> T {
+    let (txt, rxt) = mpsc::channel::<T>();
+    f(txt);
+    unwrap!(rxt.recv())
+}
+
+pub fn play_game(mut game: SGame, aplayer: EnumMap<EPlayerIndex, &dyn TPlayer>) -> SGameResult {
+    if let Some(epi_active) = game.rules.playerindex() {
+        // announce one level after the other, as long as the declarer wants to
+        while let Some(eschneiderschwarz) = game.schneider_schwarz_announceable(epi_active) {
+            if !communicate_via_channel(|txb_announce| {
+                aplayer[epi_active].ask_for_announcement(
+                    epi_active,
+                    &game,
+                    eschneiderschwarz,
+                    txb_announce,
+                );
+            }) {
+                break;
+            }
+            unwrap!(game.announce_schneider_schwarz(epi_active, eschneiderschwarz));
+        }
+    }
+    while let Some(gameaction)=game.which_player_can_do_something() {
+        if !gameaction.1.is_empty() {
+            if let Some(epi_stoss) = gameaction.1.iter()
+                .find(|epi| {
+                    communicate_via_channel(|txb_stoss| {
+                        aplayer[**epi].ask_for_stoss(
+                            **epi,
+                            &game,
+                            txb_stoss,
+                        );
+                    })
+                })
+            {
+                unwrap!(game.stoss(*epi_stoss));
+                continue;
+            }
+        }
+        unwrap!(game.zugeben(
+            communicate_via_channel(|txcard| {
+                aplayer[gameaction.0].ask_for_card(
+                    &game,
+                    txcard,
+                );
+            }),
+            gameaction.0
+        ));
+    }
+    unwrap!(game.finish())
+}
+
+pub fn run_simple_game_loop(aplayer: EnumMap<EPlayerIndex, Box<dyn TPlayer>>, n_games: usize, ruleset: SRuleSet) -> ([SAtTable; EPlayerIndex::SIZE], isize) {
+    let mut aattable = aplayer.map_into(|player| SAtTable{player, n_money:0, n_games_doubled:0, n_money_doubled:0});
     let mut n_stock = 0;
     for _i_game in 0..n_games {
-        fn communicate_via_channel<T: std::fmt::Debug>(f: impl FnOnce(mpsc::Sender<T>)) -> T {
-            let (txt, rxt) = mpsc::channel::<T>();
-            f(txt);
-            unwrap!(rxt.recv())
-        }
         let mut dealcards = SDealCards::new(ruleset.clone(), n_stock);
         while let Some(epi) = dealcards.which_player_can_do_something() {
             unwrap!(dealcards.announce_doubling(
                 epi,
                 /*b_doubling*/communicate_via_channel(|txb_doubling| {
                     aattable[epi].player.ask_for_doubling(
+                        epi,
                         dealcards.first_hand_for(epi),
+                        &ruleset,
+                        n_stock,
                         txb_doubling
                     );
                 })
             ));
         }
         let mut gamepreparations = unwrap!(dealcards.finish());
+        let ab_doubling = EPlayerIndex::map_from_fn(|epi|
+            gamepreparations.doublings.get(epi).copied().unwrap_or(false)
+        );
+        for epi in EPlayerIndex::values().filter(|epi| ab_doubling[*epi]) {
+            aattable[epi].n_games_doubled += 1;
+        }
         while let Some(epi) = gamepreparations.which_player_can_do_something() {
             info!("Asking player {} for game", epi);
             unwrap!(gamepreparations.announce_game(
@@ -45,6 +104,7 @@ pub fn run_simple_game_loop(aplayer: EnumMap<EPlayerIndex, Box<dyn TPlayer>>, n_
                         &gamepreparations.ruleset.avecrulegroup[epi],
                         stoss_and_doublings(/*vecstoss*/&[], &gamepreparations.doublings),
                         gamepreparations.n_stock,
+                        &gamepreparations.ruleset,
                         None,
                         txorules
                     );
@@ -52,17 +112,27 @@ pub fn run_simple_game_loop(aplayer: EnumMap<EPlayerIndex, Box<dyn TPlayer>>, n_
             ));
         }
         info!("Asked players if they want to play. Determining rules");
-        let stockorgame = match unwrap!(gamepreparations.finish()) {
+        let stockorhochzeitorgame = match unwrap!(gamepreparations.finish()) {
             VGamePreparationsFinish::DetermineRules(mut determinerules) => {
                 while let Some((epi, vecrulegroup_steigered))=determinerules.which_player_can_do_something() {
                     if let Some(rules) = communicate_via_channel(|txorules| {
                         aattable[epi].player.ask_for_game(
                             epi,
                             determinerules.fullhand(epi),
-                            /*gameannouncements*/&SPlayersInRound::new(SStaticEPI0{}),
+                            /*gameannouncements*/&{
+                                // only the current bid is relevant while Steigern
+                                let (epi_bid, ref rules_bid) = determinerules.tplepirules_current_bid;
+                                SPlayersInRound::new_full(
+                                    SStaticEPI0{},
+                                    EPlayerIndex::map_from_fn(|epi|
+                                        if_then_some!(epi==epi_bid, TActivelyPlayableRulesBoxClone::box_clone(rules_bid.as_ref()))
+                                    ).into_raw(),
+                                )
+                            },
                             &vecrulegroup_steigered,
                             stoss_and_doublings(/*vecstoss*/&[], &determinerules.doublings),
                             determinerules.n_stock,
+                            &determinerules.ruleset,
                             Some(determinerules.currently_offered_prio()),
                             txorules
                         );
@@ -75,51 +145,82 @@ pub fn run_simple_game_loop(aplayer: EnumMap<EPlayerIndex, Box<dyn TPlayer>>, n_
                 VStockOrT::OrT(unwrap!(determinerules.finish()))
             },
             VGamePreparationsFinish::DirectGame(game) => {
-                VStockOrT::OrT(game)
+                VStockOrT::OrT(VHochzeitOrGame::Game(game))
+            },
+            VGamePreparationsFinish::DerAlteMuss(mut deraltemuss) => {
+                while let Some((epi, vecrulegroup_forced))=deraltemuss.which_player_can_do_something() {
+                    let rules = TActivelyPlayableRulesBoxClone::box_clone(communicate_via_channel(|txrules| {
+                        aattable[epi].player.ask_for_forced_game(
+                            epi,
+                            deraltemuss.fullhand(epi),
+                            &vecrulegroup_forced,
+                            stoss_and_doublings(/*vecstoss*/&[], &deraltemuss.doublings),
+                            deraltemuss.n_stock,
+                            txrules
+                        );
+                    }));
+                    unwrap!(deraltemuss.announce_game(epi, rules));
+                }
+                VStockOrT::OrT(unwrap!(deraltemuss.finish()))
+            },
+            VGamePreparationsFinish::Schieben(mut schieben) => {
+                while let Some(epi) = schieben.which_player_can_do_something() {
+                    unwrap!(schieben.announce_schieben(
+                        epi,
+                        /*b_schieben*/communicate_via_channel(|txb_schieben| {
+                            aattable[epi].player.ask_for_schieben(
+                                epi,
+                                schieben.fullhand(epi),
+                                schieben.rules.as_ref(),
+                                stoss_and_doublings(/*vecstoss*/&[], &schieben.doublings),
+                                schieben.n_stock,
+                                txb_schieben,
+                            );
+                        })
+                    ));
+                }
+                VStockOrT::OrT(VHochzeitOrGame::Game(unwrap!(schieben.finish())))
             },
             VGamePreparationsFinish::Stock(gameresult) => {
                 VStockOrT::Stock(gameresult)
             }
         };
-        let gameresult = match stockorgame {
-            VStockOrT::OrT(mut game) => {
-                while let Some(gameaction)=game.which_player_can_do_something() {
-                    if !gameaction.1.is_empty() {
-                        if let Some(epi_stoss) = gameaction.1.iter()
-                            .find(|epi| {
-                                communicate_via_channel(|txb_stoss| {
-                                    aattable[**epi].player.ask_for_stoss(
-                                        **epi,
-                                        &game.doublings,
-                                        game.rules.as_ref(),
-                                        &game.ahand[**epi],
-                                        &game.vecstoss,
-                                        game.n_stock,
-                                        txb_stoss,
-                                    );
-                                })
-                            })
-                        {
-                            unwrap!(game.stoss(*epi_stoss));
-                            continue;
-                        }
-                    }
-                    unwrap!(game.zugeben(
-                        communicate_via_channel(|txcard| {
-                            aattable[gameaction.0].player.ask_for_card(
-                                &game,
-                                txcard,
+        let stockorgame = match stockorhochzeitorgame {
+            VStockOrT::OrT(VHochzeitOrGame::Hochzeit(mut hochzeit)) => {
+                while let Some(epi) = hochzeit.which_player_can_do_something() {
+                    let veccard_exchange = hochzeit.cards_to_exchange(epi).collect::<Vec<_>>();
+                    unwrap!(hochzeit.accept(
+                        epi,
+                        /*ocard_exchange*/communicate_via_channel(|txocard_exchange| {
+                            aattable[epi].player.ask_for_hochzeit(
+                                epi,
+                                hochzeit.fullhand(epi),
+                                hochzeit.rules.as_ref(),
+                                &veccard_exchange,
+                                stoss_and_doublings(/*vecstoss*/&[], &hochzeit.doublings),
+                                hochzeit.n_stock,
+                                txocard_exchange,
                             );
-                        }),
-                        gameaction.0
+                        })
                     ));
                 }
-                unwrap!(game.finish())
+                VStockOrT::OrT(unwrap!(hochzeit.finish()))
             },
+            VStockOrT::OrT(VHochzeitOrGame::Game(game)) => VStockOrT::OrT(game),
+            VStockOrT::Stock(gameresult) => VStockOrT::Stock(gameresult),
+        };
+        let gameresult = match stockorgame {
+            VStockOrT::OrT(game) => play_game(
+                game,
+                EPlayerIndex::map_from_fn(|epi| aattable[epi].player.as_ref()),
+            ),
             VStockOrT::Stock(gameresult) => gameresult,
         };
         gameresult.apply_payout(&mut n_stock, |epi, n_payout| {
             aattable[epi].n_money += n_payout;
+            if ab_doubling[epi] {
+                aattable[epi].n_money_doubled += n_payout;
+            }
         });
         assert_eq!(n_stock + aattable.iter().map(|attable| attable.n_money).sum::<isize>(), 0);
         skui::print_account_balance(&aattable.map(|attable| attable.n_money), n_stock);
@@ -128,3 +229,50 @@ pub fn run_simple_game_loop(aplayer: EnumMap<EPlayerIndex, Box<dyn TPlayer>>, n_
     (aattable.into_raw(), n_stock)
 }
 
+
+#[test]
+#[cfg(not(feature="dreier"))]
+fn test_play_game_announce_schneider_schwarz() {
+    use crate::ai::SAi;
+    use crate::card::card_values::*;
+    use crate::player::playercomputer::*;
+    use crate::rules::{payoutdecider::*, rulessolo::*, tests::TPayoutDeciderSoloLikeDefault};
+    let aplayer = EPlayerIndex::map_from_fn(|_epi| SPlayerComputer{
+        ai: SAi::new_cheating(/*n_rank_rules_samples*/2, /*n_suggest_card_branches*/1),
+    });
+    // declarer holding all Ober and Unter surely wins schwarz
+    let gameresult = play_game(
+        SGame::new(
+            EPlayerIndex::map_from_raw([
+                [EO, GO, HO, SO, EU, GU, HU, SU],
+                [E7, E8, E9, G7, G8, G9, S7, S8],
+                [HA, HZ, HK, H9, H8, H7, EA, EZ],
+                [EK, GA, GZ, GK, SA, SZ, SK, S9],
+            ]).map_into(|acard| acard.into()),
+            SDoublings::new(SStaticEPI0{}),
+            Some(SStossParams::new(
+                /*n_stoss_max*/4,
+                EStossTime::BeforeFirstCard,
+                /*b_announce_schneider_schwarz*/true,
+            )),
+            TRulesBoxClone::box_clone(sololike(
+                EPlayerIndex::EPI0,
+                EFarbe::Herz,
+                ESoloLike::Solo,
+                SPayoutDeciderPointBased::default_payoutdecider(/*n_payout_base*/50, /*n_payout_schneider_schwarz*/10, SLaufendeParams::new(10, 3)),
+            ).as_ref()),
+            /*n_stock*/0,
+        ),
+        EPlayerIndex::map_from_fn(|epi| &aplayer[epi] as &dyn TPlayer),
+    );
+    let game = match gameresult.stockorgame {
+        VStockOrT::OrT(game) => game,
+        VStockOrT::Stock(()) => panic!("Solo must not end in Stock"),
+    };
+    assert_eq!(game.rules.announced_schneider_schwarz(), Some(ESchneiderSchwarz::Schwarz));
+    assert_eq!(
+        gameresult.an_payout[EPlayerIndex::EPI0],
+        // base, schwarz reached and announced, 8 laufende, each paid by 3 opponents
+        (3 * (50 + (2+2)*10 + 8*10)) << game.vecstoss.len(),
+    );
+}
@@ -30,7 +30,7 @@ impl TPayoutDeciderSoloLikeDefault for SPayoutDeciderTout {
 }
 
 #[cfg(test)]
-pub fn make_stich_vector(vectplepiacard_stich: &[(EPlayerIndex, [SCard; 4])]) -> Vec<SStich> {
+pub fn make_stich_vector(vectplepiacard_stich: &[(EPlayerIndex, [SCard; EPlayerIndex::SIZE])]) -> Vec<SStich> {
     vectplepiacard_stich.iter()
         .map(|&(epi, acard)| {
             SStich::new_full(epi, acard)
@@ -59,7 +59,7 @@ pub struct SGameAnalysis {
     pub duration: Duration,
 }
 
-pub fn analyze_game(str_description: &str, str_link: &str, game_in: SGame) -> SGameAnalysis {
+pub fn analyze_game(str_description: &str, str_link: &str, game_in: SGame, b_objective_parties: bool, payoutranking: VPayoutRanking) -> SGameAnalysis {
     let instant_begin = Instant::now();
     let mut vecanalysisimpr = Vec::new();
     let an_payout = unwrap!(game_in.clone().finish()).an_payout;
@@ -83,14 +83,25 @@ pub fn analyze_game(str_description: &str, str_link: &str, game_in: SGame) -> SG
                 let determinebestcard = SDetermineBestCard::new_from_game(game);
                 macro_rules! look_for_mistakes{($itahand: expr,) => {{
                     if determinebestcard.single_allowed_card().is_none() { // there is an actual choice
-                        let determinebestcardresult = determine_best_card(
-                            &determinebestcard,
-                            $itahand,
-                            &|_,_| (/*no filtering*/),
-                            &SMinReachablePayout::new_from_game(game),
-                            /*fn_visualizer*/|_,_,_| SNoVisualization,
+                        macro_rules! forward{(($foreachsnapshot: ident),) => {{ // TODORUST generic closures
+                            determine_best_card(
+                                &determinebestcard,
+                                $itahand,
+                                &|_,_| (/*no filtering*/),
+                                &$foreachsnapshot::new_from_game(game),
+                                /*fn_visualizer*/|_,_,_| SNoVisualization,
+                            )
+                        }}}
+                        let determinebestcardresult = cartesian_match!(
+                            forward,
+                            match (b_objective_parties) {
+                                true => (SPartyAwarePayout),
+                                false => (SMinReachablePayout),
+                            },
+                        );
+                        let (veccard, minmax) = determinebestcardresult.cards_with_maximum_value_by(|minmax_lhs, minmax_rhs|
+                            minmax_lhs.compare(minmax_rhs, payoutranking)
                         );
-                        let (veccard, minmax) = determinebestcardresult.cards_with_maximum_value();
                         if 
                             !veccard.contains(&card) // TODO can we improve this?
                             && an_payout[epi]<minmax.t_min.min()
@@ -114,6 +125,7 @@ pub fn analyze_game(str_description: &str, str_link: &str, game_in: SGame) -> SG
                             &game.stichseq,
                             game.ahand[determinebestcard.epi_fixed].clone(),
                             determinebestcard.epi_fixed,
+                            game.open_hand(),
                             game.rules.as_ref(),
                         ),
                     )
@@ -271,7 +283,7 @@ pub struct SGameWithDesc {
     pub resgameresult: Result<SGameResult, failure::Error>,
 }
 
-pub fn analyze_games(path_analysis: &std::path::Path, fn_link: impl Fn(&str)->String, itgamewithdesc: impl Iterator<Item=SGameWithDesc>, b_include_no_findings: bool) -> Result<(), failure::Error> {
+pub fn analyze_games(path_analysis: &std::path::Path, fn_link: impl Fn(&str)->String, itgamewithdesc: impl Iterator<Item=SGameWithDesc>, b_include_no_findings: bool, b_objective_parties: bool, payoutranking: VPayoutRanking) -> Result<(), failure::Error> {
     create_dir_if_not_existent(path_analysis)?;
     generate_html_auxiliary_files(path_analysis)?;
     let str_date = format!("{}", chrono::Local::now().format("%Y%m%d%H%M%S"));
@@ -297,13 +309,10 @@ pub fn analyze_games(path_analysis: &std::path::Path, fn_link: impl Fn(&str)->St
                         str_index_html += &format!(
                             r#"<tr>
                                 <td>
-                                    Stock: {}/{}/{}/{}
+                                    Stock: {}
                                 </td>
                             </tr>"#,
-                            gameresult.an_payout[EPlayerIndex::EPI0],
-                            gameresult.an_payout[EPlayerIndex::EPI1],
-                            gameresult.an_payout[EPlayerIndex::EPI2],
-                            gameresult.an_payout[EPlayerIndex::EPI3],
+                            gameresult.an_payout.iter().join("/"),
                         );
                     }
                 },
@@ -312,7 +321,7 @@ pub fn analyze_games(path_analysis: &std::path::Path, fn_link: impl Fn(&str)->St
                     let path_analysis_game = path_analysis.join(gamewithdesc.str_description.replace("/", "_").replace(".", "_"));
                     create_dir_if_not_existent(&path_analysis_game)?;
                     let path = path_analysis_game.join("analysis.html");
-                    let gameanalysis = analyze_game(&gamewithdesc.str_description, &fn_link(&gamewithdesc.str_description), game);
+                    let gameanalysis = analyze_game(&gamewithdesc.str_description, &fn_link(&gamewithdesc.str_description), game, b_objective_parties, payoutranking);
                     let path = write_html(path, &gameanalysis.str_html)?;
                     let n_findings_simulating = gameanalysis.n_findings_simulating;
                     let n_findings_cheating = gameanalysis.n_findings_cheating;
@@ -12,7 +12,10 @@ use std::sync::mpsc;
 pub trait TPlayer {
     fn ask_for_doubling(
         &self,
+        epi: EPlayerIndex,
         veccard: &[SCard],
+        ruleset: &SRuleSet,
+        n_stock: isize,
         txb_doubling: mpsc::Sender<bool>,
     );
 
@@ -26,19 +29,57 @@ pub trait TPlayer {
         vecrulegroup: &'rules [SRuleGroup],
         tpln_stoss_doubling: (usize, usize),
         n_stock: isize,
+        ruleset: &SRuleSet,
         otplepiprio: Option<(EPlayerIndex, VGameAnnouncementPriority)>,
         txorules: mpsc::Sender<Option<&'rules dyn TActivelyPlayableRules>>
     );
 
+    fn ask_for_forced_game<'rules>(
+        &self,
+        epi: EPlayerIndex,
+        hand: SFullHand,
+        vecrulegroup: &'rules [SRuleGroup],
+        tpln_stoss_doubling: (usize, usize),
+        n_stock: isize,
+        txrules: mpsc::Sender<&'rules dyn TActivelyPlayableRules>
+    );
+
     fn ask_for_stoss(
         &self,
         epi: EPlayerIndex,
-        doublings: &SDoublings,
+        game: &SGame,
+        txb: mpsc::Sender<bool>,
+    );
+
+    // asked only for the next announceable level, i.e. Schwarz is asked after Schneider has been announced
+    fn ask_for_announcement(
+        &self,
+        epi: EPlayerIndex,
+        game: &SGame,
+        eschneiderschwarz: ESchneiderSchwarz,
+        txb: mpsc::Sender<bool>,
+    );
+
+    fn ask_for_schieben(
+        &self,
+        epi: EPlayerIndex,
+        hand: SFullHand,
         rules: &dyn TRules,
-        hand: &SHand,
-        vecstoss: &[SStoss],
+        tpln_stoss_doubling: (usize, usize),
         n_stock: isize,
-        txb: mpsc::Sender<bool>,
+        txb_schieben: mpsc::Sender<bool>,
+    );
+
+    // None declines the offer, Some(card) accepts it and gives card in exchange for the offered trumpf
+    fn ask_for_hochzeit(
+        &self,
+        epi: EPlayerIndex,
+        hand: SFullHand,
+        rules: &dyn TRules,
+        veccard_exchange: &[SCard],
+        tpln_stoss_doubling: (usize, usize),
+        n_stock: isize,
+        txocard_exchange: mpsc::Sender<Option<SCard>>,
     );
 
     fn name(&self) -> &str;
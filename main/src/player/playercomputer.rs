@@ -1,10 +1,4 @@
-use crate::ai::{
-    handiterators::forever_rand_hands,
-    suspicion::{
-        explore_snapshots, SMinReachablePayout, SNoVisualization,
-    },
-    *,
-};
+use crate::ai::{suspicion::SNoVisualization, *};
 use crate::game::*;
 use crate::player::*;
 use crate::primitives::*;
@@ -16,47 +10,20 @@ pub struct SPlayerComputer {
     pub ai : SAi,
 }
 
-impl TPlayer for SPlayerComputer {
-    fn ask_for_doubling(
-        &self,
-        veccard: &[SCard],
-        txb_doubling: mpsc::Sender<bool>,
-    ) {
-        txb_doubling.send(
-            veccard.iter()
-                .filter(|card| {
-                    ESchlag::Ober==card.schlag() || ESchlag::Unter==card.schlag() || EFarbe::Herz==card.farbe()
-                })
-                .count() >= 3
-            || EFarbe::values().any(|efarbe| {
-                4==veccard.iter()
-                    .filter(|card| efarbe==card.farbe())
-                    .count()
-            })
-        ).ok(); // TODO more intelligent doubling strategy
-    }
-
-    fn ask_for_card(&self, game: &SGame, txcard: mpsc::Sender<SCard>) {
-        txcard.send(self.ai.suggest_card(game, /*fn_visualizer*/|_,_,_| SNoVisualization)).ok();
-    }
-
-    fn ask_for_game<'rules>(
+impl SPlayerComputer {
+    fn best_rules<'rules>(
         &self,
-        _epi: EPlayerIndex,
+        itorules: impl Iterator<Item=Option<&'rules dyn TActivelyPlayableRules>>,
         hand: SFullHand,
-        _gameannouncements : &SGameAnnouncements,
-        vecrulegroup: &'rules [SRuleGroup],
         tpln_stoss_doubling: (usize, usize),
         n_stock: isize,
-        _otplepiprio: Option<(EPlayerIndex, VGameAnnouncementPriority)>,
-        txorules: mpsc::Sender<Option<&'rules dyn TActivelyPlayableRules>>
-    ) {
-        // TODO: implement a more intelligent decision strategy
-        unwrap!(txorules.send(unwrap!(allowed_rules(vecrulegroup, hand)
+        fn_rank_none: impl Fn() -> f64,
+    ) -> Option<&'rules dyn TActivelyPlayableRules> {
+        unwrap!(itorules
             .map(|orules| (
                 orules,
-                orules.map_or(
-                    0., // TODO how to rank None?
+                orules.map_or_else(
+                    &fn_rank_none,
                     |rules| self.ai.rank_rules(
                         hand,
                         /*epi_rank*/rules.active_playerindex(),
@@ -71,69 +38,195 @@ impl TPlayer for SPlayerComputer {
                 assert!(!f_payout_avg_rhs.is_nan());
                 unwrap!(f_payout_avg_lhs.partial_cmp(&f_payout_avg_rhs))
             })
-        ).0));
+        ).0
     }
+}
 
-    fn ask_for_stoss(
+impl TPlayer for SPlayerComputer {
+    fn ask_for_doubling(
         &self,
         epi: EPlayerIndex,
-        doublings: &SDoublings,
-        rules: &dyn TRules,
-        hand: &SHand,
-        vecstoss: &[SStoss],
+        veccard: &[SCard],
+        ruleset: &SRuleSet,
         n_stock: isize,
+        txb_doubling: mpsc::Sender<bool>,
+    ) {
+        txb_doubling.send(self.ai.suggest_doubling(epi, veccard, ruleset, n_stock)).ok();
+    }
+
+    fn ask_for_card(&self, game: &SGame, txcard: mpsc::Sender<SCard>) {
+        txcard.send(self.ai.suggest_card(game, /*fn_visualizer*/|_,_,_| SNoVisualization)).ok();
+    }
+
+    fn ask_for_game<'rules>(
+        &self,
+        epi: EPlayerIndex,
+        hand: SFullHand,
+        gameannouncements : &SGameAnnouncements,
+        vecrulegroup: &'rules [SRuleGroup],
+        tpln_stoss_doubling: (usize, usize),
+        n_stock: isize,
+        ruleset: &SRuleSet,
+        _otplepiprio: Option<(EPlayerIndex, VGameAnnouncementPriority)>, // current bid is contained in gameannouncements
+        txorules: mpsc::Sender<Option<&'rules dyn TActivelyPlayableRules>>
+    ) {
+        unwrap!(txorules.send(self.best_rules(
+            allowed_rules(vecrulegroup, hand),
+            hand,
+            tpln_stoss_doubling,
+            n_stock,
+            /*fn_rank_none*/|| self.ai.rank_none(epi, hand, gameannouncements, tpln_stoss_doubling, n_stock, ruleset),
+        )));
+    }
+
+    fn ask_for_forced_game<'rules>(
+        &self,
+        _epi: EPlayerIndex,
+        hand: SFullHand,
+        vecrulegroup: &'rules [SRuleGroup],
+        tpln_stoss_doubling: (usize, usize),
+        n_stock: isize,
+        txrules: mpsc::Sender<&'rules dyn TActivelyPlayableRules>
+    ) {
+        unwrap!(txrules.send(unwrap!(self.best_rules(
+            allowed_rules(vecrulegroup, hand),
+            hand,
+            tpln_stoss_doubling,
+            n_stock,
+            /*fn_rank_none*/|| panic!("vecrulegroup does not offer \"Nothing\" here"),
+        ))));
+    }
+
+    fn ask_for_stoss(
+        &self,
+        epi: EPlayerIndex,
+        game: &SGame,
         txb: mpsc::Sender<bool>,
     ) {
-        let n_samples_per_stoss = 5; // TODO move to ai, make adjustable
-        let ekurzlang = EKurzLang::from_cards_per_player(hand.cards().len());
-        let mut vectplahandf_suspicion = forever_rand_hands(/*stichseq*/&SStichSequence::new(ekurzlang), hand.clone(), epi, rules)
-            .take(2*n_samples_per_stoss)
-            .map(|ahand| {
-                let f_rank_rules = rules.playerindex().map_or(0f64, |epi_active| {
-                    if epi!=epi_active {
-                        self.ai.rank_rules(
-                            SFullHand::new(ahand[epi_active].cards(), ekurzlang),
-                            /*epi_rank*/epi_active,
-                            rules,
-                            stoss_and_doublings(vecstoss, doublings),
-                            n_stock,
-                        )
-                    } else {
-                        0f64
-                    }
-                });
-                (ahand, f_rank_rules)
-            })
-            .collect::<Vec<_>>();
-        vectplahandf_suspicion.sort_unstable_by(|&(ref _ahand_l, f_rank_l), &(ref _ahand_r, f_rank_r)|
-            unwrap!(f_rank_r.partial_cmp(&f_rank_l))
-        );
-        vectplahandf_suspicion.truncate(n_samples_per_stoss);
-        assert_eq!(n_samples_per_stoss, vectplahandf_suspicion.len());
+        unwrap!(txb.send(self.ai.suggest_stoss(game, epi)));
+    }
+
+    fn ask_for_announcement(
+        &self,
+        epi: EPlayerIndex,
+        game: &SGame,
+        eschneiderschwarz: ESchneiderSchwarz,
+        txb: mpsc::Sender<bool>,
+    ) {
+        // announce if the additional payout outweighs the risk of missing the announced level
+        let hand = SFullHand::new(game.ahand[epi].cards(), game.kurzlang());
+        let tpln_stoss_doubling = stoss_and_doublings(&game.vecstoss, &game.doublings);
         unwrap!(txb.send(
-            vectplahandf_suspicion.into_iter()
-                .map(|(mut ahand, _f_rank_rules)| {
-                    explore_snapshots(
-                        &mut ahand,
-                        rules,
-                        &mut SStichSequence::new(ekurzlang),
-                        &branching_factor(|_stichseq| (1, 2)),
-                        &SMinReachablePayout::new(
-                            rules,
-                            epi,
-                            /*tpln_stoss_doubling*/stoss_and_doublings(vecstoss, doublings),
-                            n_stock,
-                        ),
-                        &mut SNoVisualization,
-                    ).t_min[epi]
-                })
-                .sum::<isize>().as_num::<f64>()
-                / n_samples_per_stoss.as_num::<f64>()
-                > 10f64
-        ))
+            self.ai.rank_rules(hand, epi, unwrap!(game.rules.with_announced_schneider_schwarz(eschneiderschwarz)).as_ref(), tpln_stoss_doubling, game.n_stock)
+                > self.ai.rank_rules(hand, epi, game.rules.as_ref(), tpln_stoss_doubling, game.n_stock)
+        ));
+    }
+
+    fn ask_for_schieben(
+        &self,
+        epi: EPlayerIndex,
+        hand: SFullHand,
+        rules: &dyn TRules,
+        tpln_stoss_doubling: (usize, usize),
+        n_stock: isize,
+        txb_schieben: mpsc::Sender<bool>,
+    ) {
+        // push only if we win money even for the worst sampled distribution of the other cards
+        unwrap!(txb_schieben.send(
+            0 < self.ai.rank_rules_min(hand, epi, rules, tpln_stoss_doubling, n_stock)
+        ));
+    }
+
+    fn ask_for_hochzeit(
+        &self,
+        epi: EPlayerIndex,
+        hand: SFullHand,
+        rules: &dyn TRules,
+        veccard_exchange: &[SCard],
+        tpln_stoss_doubling: (usize, usize),
+        n_stock: isize,
+        txocard_exchange: mpsc::Sender<Option<SCard>>,
+    ) {
+        // accept if playing with the offering player promises more than playing against them, giving away as few points as possible
+        unwrap!(txocard_exchange.send(
+            veccard_exchange.iter().copied()
+                .min_by_key(|card| card_points::points_card(*card))
+                .filter(|_card_exchange|
+                    self.ai.rank_rules(hand, epi, unwrap!(rules.with_offer_accepted(epi)).as_ref(), tpln_stoss_doubling, n_stock)
+                        > self.ai.rank_rules(hand, epi, rules, tpln_stoss_doubling, n_stock)
+                )
+        ));
     }
 
     fn name(&self) -> &str {
         "SPlayerComputer" // TODO
     }
 }
+
+#[test]
+#[cfg(not(feature="dreier"))]
+fn test_ask_for_game_ranks_none() {
+    use crate::card::card_values::*;
+    // no Rufspiel: a sampled partner could carry the weak hand, making the test random
+    let ruleset = unwrap!(SRuleSet::from_string(
+        r"
+        base-price=10
+        solo-price=50
+        lauf-min=3
+        [solo]
+        [stock]
+        price=5
+        ",
+    ));
+    let playercomputer = SPlayerComputer{ai: SAi::new_cheating(/*n_rank_rules_samples*/10, /*n_suggest_card_branches*/1)};
+    let ask_for_game = |acard: [SCard; 8], gameannouncements: &SGameAnnouncements| {
+        let epi = unwrap!(gameannouncements.current_playerindex());
+        let hand = SFullHand::new(&acard, EKurzLang::Lang);
+        if gameannouncements.iter().all(|(_epi, orules)| orules.is_none()) {
+            // nobody wants to play so far, so we expect to pay into the stock
+            assert_eq!(
+                playercomputer.ai.rank_none(epi, hand, gameannouncements, /*tpln_stoss_doubling*/(0, 0), /*n_stock*/0, &ruleset),
+                -5.,
+            );
+        }
+        let (txorules, rxorules) = mpsc::channel();
+        playercomputer.ask_for_game(
+            epi,
+            hand,
+            gameannouncements,
+            &ruleset.avecrulegroup[epi],
+            /*tpln_stoss_doubling*/(0, 0),
+            /*n_stock*/0,
+            &ruleset,
+            /*otplepiprio*/None,
+            txorules,
+        );
+        unwrap!(rxorules.recv()).map(|rules| rules.to_string())
+    };
+    let mut gameannouncements = SGameAnnouncements::new(SStaticEPI0{});
+    assert_eq!(ask_for_game([E7, E8, G7, G8, H7, H8, S7, S8], &gameannouncements), None);
+    gameannouncements.push(None);
+    assert!(ask_for_game([EO, GO, HO, SO, EU, GU, HU, SU], &gameannouncements).is_some());
+    // "der Alte muss": passing does not lead to the stock
+    let ruleset_der_alte_muss = unwrap!(SRuleSet::from_string(
+        r"
+        base-price=10
+        solo-price=50
+        lauf-min=3
+        [solo]
+        [stock]
+        price=5
+        [der-alte-muss]
+        ",
+    ));
+    let rank_none = |acard: [SCard; 8]| playercomputer.ai.rank_none(
+        EPlayerIndex::EPI0,
+        SFullHand::new(&acard, EKurzLang::Lang),
+        &SGameAnnouncements::new(SStaticEPI0{}),
+        /*tpln_stoss_doubling*/(0, 0),
+        /*n_stock*/0,
+        &ruleset_der_alte_muss,
+    );
+    assert!(rank_none([EO, E7, E8, G7, G8, H7, H8, S7]) < -5.); // we must play and lose the solo
+    assert_eq!(rank_none([E7, E8, G7, G8, H7, H8, S7, S8]), 0.); // someone else must play
+}
@@ -45,10 +45,51 @@ fn choose_ruleset_or_rules<'t, T>(
     )
 }
 
+fn choose_rules<'rules>(
+    hand: SFullHand,
+    vecrulegroup: &'rules [SRuleGroup],
+    otplepiprio: &Option<(EPlayerIndex, VGameAnnouncementPriority)>,
+) -> Option<&'rules dyn TActivelyPlayableRules> {
+    let vecrulegroup : Vec<&SRuleGroup> = vecrulegroup.iter()
+        .filter(|rulegroup| 0 < rulegroup.allowed_rules(hand).count())
+        .collect();
+    loop {
+        let vecoorules : Vec<Option<Option<&dyn TActivelyPlayableRules>>> = std::iter::once(None) // stands for "back"
+            .chain(
+                choose_ruleset_or_rules(
+                    &SHand::new_from_iter(hand.get().iter().copied()),
+                    &vecrulegroup,
+                    |rulegroup| rulegroup.str_name.clone(),
+                    |i_rulegroup_chosen| vecrulegroup[i_rulegroup_chosen].vecorules[0].as_ref().map(|rules| rules.as_ref()),
+                    otplepiprio,
+                )
+                    .allowed_rules(hand)
+                    .map(Some)
+            )
+            .collect();
+        if let Some(orules) = *choose_ruleset_or_rules(
+            &SHand::new_from_iter(hand.get().iter().copied()),
+            &vecoorules,
+            |oorules| match *oorules {
+                None => "Back".to_string(),
+                Some(None) => "Nothing".to_string(),
+                Some(Some(rules)) => rules.to_string()
+            },
+            |i_oorules_chosen| vecoorules[i_oorules_chosen].and_then(|orules| orules),
+            otplepiprio,
+        ) {
+            return orules;
+        }
+    }
+}
+
 impl TPlayer for SPlayerHuman {
     fn ask_for_doubling(
         &self,
+        _epi: EPlayerIndex,
         veccard: &[SCard],
+        _ruleset: &SRuleSet,
+        _n_stock: isize,
         txb_doubling: mpsc::Sender<bool>,
     ) {
         let ab_doubling = [false, true];
@@ -90,6 +131,9 @@ impl TPlayer for SPlayerHuman {
                         skui::wprintln(ncwin, &format!("AI: {}", card));
                     }
                     skui::print_hand(hand.cards(), Some(i_card_chosen));
+                    if let Some((epi_open, hand_open)) = game.open_hand().filter(|&(epi_open, _)| epi_open!=epi) {
+                        skui::wprintln(ncwin, &format!("Open hand of {}: {}", epi_open, hand_open));
+                    }
                     skui::print_game_info(game.rules.as_ref(), &game.doublings, &game.vecstoss);
                 },
                 || {
@@ -116,52 +160,31 @@ impl TPlayer for SPlayerHuman {
         vecrulegroup: &'rules [SRuleGroup],
         _tpln_stoss_doubling: (usize, usize),
         _n_stock: isize,
+        _ruleset: &SRuleSet,
         otplepiprio: Option<(EPlayerIndex, VGameAnnouncementPriority)>,
         txorules: mpsc::Sender<Option<&'rules dyn TActivelyPlayableRules>>,
     ) {
         skui::print_game_announcements(epi, gameannouncements);
-        let vecrulegroup : Vec<&SRuleGroup> = vecrulegroup.iter()
-            .filter(|rulegroup| 0 < rulegroup.allowed_rules(hand).count())
-            .collect();
-        loop {
-            let vecoorules : Vec<Option<Option<&dyn TActivelyPlayableRules>>> = std::iter::once(None) // stands for "back"
-                .chain(
-                    choose_ruleset_or_rules(
-                        &SHand::new_from_iter(hand.get().iter().copied()),
-                        &vecrulegroup,
-                        |rulegroup| rulegroup.str_name.clone(),
-                        |i_rulegroup_chosen| vecrulegroup[i_rulegroup_chosen].vecorules[0].as_ref().map(|rules| rules.as_ref()),
-                        &otplepiprio,
-                    )
-                        .allowed_rules(hand)
-                        .map(Some)
-                )
-                .collect();
-            if let Some(orules) = *choose_ruleset_or_rules(
-                &SHand::new_from_iter(hand.get().iter().copied()),
-                &vecoorules,
-                |oorules| match *oorules {
-                    None => "Back".to_string(),
-                    Some(None) => "Nothing".to_string(),
-                    Some(Some(rules)) => rules.to_string()
-                },
-                |i_oorules_chosen| vecoorules[i_oorules_chosen].and_then(|orules| orules),
-                &otplepiprio,
-            ) {
-                unwrap!(txorules.send(orules));
-                return;
-            }
-        }
+        unwrap!(txorules.send(choose_rules(hand, vecrulegroup, &otplepiprio)));
     }
 
-    fn ask_for_stoss(
+    fn ask_for_forced_game<'rules>(
         &self,
         _epi: EPlayerIndex,
-        doublings: &SDoublings,
-        rules: &dyn TRules,
-        hand: &SHand,
-        vecstoss: &[SStoss],
+        hand: SFullHand,
+        vecrulegroup: &'rules [SRuleGroup],
+        _tpln_stoss_doubling: (usize, usize),
         _n_stock: isize,
+        txrules: mpsc::Sender<&'rules dyn TActivelyPlayableRules>,
+    ) {
+        // vecrulegroup does not offer "Nothing" here
+        unwrap!(txrules.send(unwrap!(choose_rules(hand, vecrulegroup, /*otplepiprio*/&None))));
+    }
+
+    fn ask_for_stoss(
+        &self,
+        epi: EPlayerIndex,
+        game: &SGame,
         txb: mpsc::Sender<bool>,
     ) {
         let ab_stoss = [false, true];
@@ -171,23 +194,118 @@ impl TPlayer for SPlayerHuman {
             |_| true, // all alternatives allowed
             |ncwin, i_b_stoss_chosen, ob_stoss_suggest| {
                 assert!(ob_stoss_suggest.is_none());
-                skui::print_game_info(rules, doublings, vecstoss);
+                skui::print_game_info(game.rules.as_ref(), &game.doublings, &game.vecstoss);
                 {
-                    let mut veccard = hand.cards().clone();
-                    rules.sort_cards_first_trumpf_then_farbe(veccard.as_mut_slice());
+                    let mut veccard = game.ahand[epi].cards().clone();
+                    game.rules.sort_cards_first_trumpf_then_farbe(veccard.as_mut_slice());
                     skui::print_hand(&veccard, None);
                 }
                 for (i_b_stoss, b_stoss) in ab_stoss.iter().enumerate() {
-                    skui::wprintln(ncwin, &format!("{} {} {}",
+                    skui::wprintln(ncwin, &format!("{} {} {:?}",
                         if i_b_stoss==i_b_stoss_chosen {"*"} else {" "},
                         if *b_stoss {"Give"} else {"No"},
-                        { match vecstoss.len() {
-                            0 => "Kontra",
-                            1 => "Re",
-                            2 => "Sup",
-                            3 => "Hirsch",
-                            _ => panic!() // currently only quadruple stoss supported
-                        } },
+                        unwrap!(EStoss::next(&game.vecstoss)),
+                    ));
+                }
+            },
+            || None, // TODO implement suggestions
+        )))
+    }
+
+    fn ask_for_announcement(
+        &self,
+        epi: EPlayerIndex,
+        game: &SGame,
+        eschneiderschwarz: ESchneiderSchwarz,
+        txb: mpsc::Sender<bool>,
+    ) {
+        let ab_announce = [false, true];
+        unwrap!(txb.send(*skui::ask_for_alternative(
+            &ab_announce,
+            &skui::choose_alternative_from_list_key_bindings(),
+            |_| true, // all alternatives allowed
+            |ncwin, i_b_announce_chosen, ob_announce_suggest| {
+                assert!(ob_announce_suggest.is_none());
+                skui::print_game_info(game.rules.as_ref(), &game.doublings, &game.vecstoss);
+                {
+                    let mut veccard = game.ahand[epi].cards().clone();
+                    game.rules.sort_cards_first_trumpf_then_farbe(veccard.as_mut_slice());
+                    skui::print_hand(&veccard, None);
+                }
+                for (i_b_announce, b_announce) in ab_announce.iter().enumerate() {
+                    skui::wprintln(ncwin, &format!("{} {} {:?}",
+                        if i_b_announce==i_b_announce_chosen {"*"} else {" "},
+                        if *b_announce {"Announce"} else {"Do not announce"},
+                        eschneiderschwarz,
+                    ));
+                }
+            },
+            || None, // TODO implement suggestions
+        )))
+    }
+
+    fn ask_for_schieben(
+        &self,
+        _epi: EPlayerIndex,
+        hand: SFullHand,
+        rules: &dyn TRules,
+        _tpln_stoss_doubling: (usize, usize),
+        _n_stock: isize,
+        txb_schieben: mpsc::Sender<bool>,
+    ) {
+        let ab_schieben = [false, true];
+        unwrap!(txb_schieben.send(*skui::ask_for_alternative(
+            &ab_schieben,
+            &skui::choose_alternative_from_list_key_bindings(),
+            |_| true, // all alternatives allowed
+            |ncwin, i_b_schieben_chosen, ob_schieben_suggest| {
+                assert!(ob_schieben_suggest.is_none());
+                skui::wprintln(ncwin, &format!("{}", rules));
+                {
+                    let mut veccard = hand.get().to_vec();
+                    rules.sort_cards_first_trumpf_then_farbe(veccard.as_mut_slice());
+                    skui::print_hand(&veccard, None);
+                }
+                for (i_b_schieben, b_schieben) in ab_schieben.iter().enumerate() {
+                    skui::wprintln(ncwin, &format!("{} {}",
+                        if i_b_schieben==i_b_schieben_chosen {"*"} else {" "},
+                        if *b_schieben {"Schieben"} else {"Nicht schieben"},
+                    ));
+                }
+            },
+            || None, // TODO implement suggestions
+        )))
+    }
+
+    fn ask_for_hochzeit(
+        &self,
+        _epi: EPlayerIndex,
+        hand: SFullHand,
+        rules: &dyn TRules,
+        veccard_exchange: &[SCard],
+        _tpln_stoss_doubling: (usize, usize),
+        _n_stock: isize,
+        txocard_exchange: mpsc::Sender<Option<SCard>>,
+    ) {
+        let vecocard_exchange = std::iter::once(None)
+            .chain(veccard_exchange.iter().copied().map(Some))
+            .collect::<Vec<_>>();
+        unwrap!(txocard_exchange.send(*skui::ask_for_alternative(
+            &vecocard_exchange,
+            &skui::choose_alternative_from_list_key_bindings(),
+            |_| true, // all alternatives allowed
+            |ncwin, i_ocard_chosen, oocard_suggest| {
+                assert!(oocard_suggest.is_none());
+                skui::wprintln(ncwin, &format!("{}", rules));
+                {
+                    let mut veccard = hand.get().to_vec();
+                    rules.sort_cards_first_trumpf_then_farbe(veccard.as_mut_slice());
+                    skui::print_hand(&veccard, None);
+                }
+                for (i_ocard, ocard) in vecocard_exchange.iter().enumerate() {
+                    skui::wprintln(ncwin, &format!("{} {}",
+                        if i_ocard==i_ocard_chosen {"*"} else {" "},
+                        if let Some(card) = ocard {format!("Annehmen, {} abgeben", card)} else {"Nicht annehmen".to_string()},
                     ));
                 }
             },
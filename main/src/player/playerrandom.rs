@@ -14,7 +14,10 @@ pub struct SPlayerRandom<FnCheckAskForCard> {
 impl<FnCheckAskForCard: Fn(&SGame)> TPlayer for SPlayerRandom<FnCheckAskForCard> {
     fn ask_for_doubling(
         &self,
+        _epi: EPlayerIndex,
         _veccard: &[SCard],
+        _ruleset: &SRuleSet,
+        _n_stock: isize,
         txb_doubling: mpsc::Sender<bool>,
     ) {
         unwrap!(txb_doubling.send(rand::random()));
@@ -40,6 +43,7 @@ impl<FnCheckAskForCard: Fn(&SGame)> TPlayer for SPlayerRandom<FnCheckAskForCard>
         vecrulegroup: &'rules [SRuleGroup],
         _tpln_stoss_doubling: (usize, usize),
         _n_stock: isize,
+        _ruleset: &SRuleSet,
         _otplepiprio: Option<(EPlayerIndex, VGameAnnouncementPriority)>,
         txorules: mpsc::Sender<Option<&'rules dyn TActivelyPlayableRules>>
     ) {
@@ -48,19 +52,68 @@ impl<FnCheckAskForCard: Fn(&SGame)> TPlayer for SPlayerRandom<FnCheckAskForCard>
         ));
     }
 
-    fn ask_for_stoss(
+    fn ask_for_forced_game<'rules>(
         &self,
         _epi: EPlayerIndex,
-        _doublings: &SDoublings,
-        _rules: &dyn TRules,
-        _hand: &SHand,
-        _vecstoss: &[SStoss],
+        hand: SFullHand,
+        vecrulegroup: &'rules [SRuleGroup],
+        _tpln_stoss_doubling: (usize, usize),
         _n_stock: isize,
+        txrules: mpsc::Sender<&'rules dyn TActivelyPlayableRules>
+    ) {
+        unwrap!(txrules.send(
+            unwrap!(unwrap!(allowed_rules(vecrulegroup, hand).choose(&mut rand::thread_rng())))
+        ));
+    }
+
+    fn ask_for_stoss(
+        &self,
+        _epi: EPlayerIndex,
+        _game: &SGame,
+        txb: mpsc::Sender<bool>,
+    ) {
+        unwrap!(txb.send(rand::random()));
+    }
+
+    fn ask_for_announcement(
+        &self,
+        _epi: EPlayerIndex,
+        _game: &SGame,
+        _eschneiderschwarz: ESchneiderSchwarz,
         txb: mpsc::Sender<bool>,
     ) {
         unwrap!(txb.send(rand::random()));
     }
 
+    fn ask_for_schieben(
+        &self,
+        _epi: EPlayerIndex,
+        _hand: SFullHand,
+        _rules: &dyn TRules,
+        _tpln_stoss_doubling: (usize, usize),
+        _n_stock: isize,
+        txb_schieben: mpsc::Sender<bool>,
+    ) {
+        unwrap!(txb_schieben.send(rand::random()));
+    }
+
+    fn ask_for_hochzeit(
+        &self,
+        _epi: EPlayerIndex,
+        _hand: SFullHand,
+        _rules: &dyn TRules,
+        veccard_exchange: &[SCard],
+        _tpln_stoss_doubling: (usize, usize),
+        _n_stock: isize,
+        txocard_exchange: mpsc::Sender<Option<SCard>>,
+    ) {
+        unwrap!(txocard_exchange.send(
+            unwrap!(std::iter::once(None)
+                .chain(veccard_exchange.iter().copied().map(Some))
+                .choose(&mut rand::thread_rng()))
+        ));
+    }
+
     fn name(&self) -> &str {
         "SPlayerRandom" // TODO
     }
@@ -1,4 +1,5 @@
 use crate::util::*;
+use crate::primitives::eplayerindex::EPlayerIndex;
 use std::fmt;
 use serde::{Serializer};
 
@@ -52,22 +53,32 @@ pub enum EKurzLang {
 }
 
 impl EKurzLang {
-    pub fn cards_per_player(self) -> usize {
+    fn cards_total(self) -> usize {
         match self {
-            Self::Kurz => 6,
-            Self::Lang => 8,
+            Self::Kurz => 24,
+            Self::Lang => 32,
         }
     }
 
+    pub fn can_be_dealt(self) -> bool {
+        self.cards_total() % EPlayerIndex::SIZE == 0
+    }
+
+    pub fn cards_per_player(self) -> usize {
+        assert!(self.can_be_dealt(), "{:?} cannot be dealt to {} players.", self, EPlayerIndex::SIZE);
+        self.cards_total() / EPlayerIndex::SIZE
+    }
+
     fn internal_from_cards_per_player<R, FnOk: FnOnce(EKurzLang)->R, FnErr: FnOnce()->R>(
         n_cards_per_player: usize,
         fn_ok: FnOk,
         fn_err: FnErr,
     ) -> R {
-        match n_cards_per_player {
-            6 => fn_ok(EKurzLang::Kurz),
-            8 => fn_ok(EKurzLang::Lang),
-            _ => fn_err(),
+        match [EKurzLang::Kurz, EKurzLang::Lang].into_iter()
+            .find(|ekurzlang| ekurzlang.can_be_dealt() && ekurzlang.cards_per_player()==n_cards_per_player)
+        {
+            Some(ekurzlang) => fn_ok(ekurzlang),
+            None => fn_err(),
         }
     }
 
@@ -3,12 +3,18 @@ use arrayvec::{self, ArrayVec};
 use std::{fmt, ops::Index, slice, str::FromStr};
 use serde_repr::Serialize_repr;
 
+#[cfg(not(feature="dreier"))]
 plain_enum_mod!(modepi, derive(Serialize_repr,), map_derive(), EPlayerIndex {
     EPI0, EPI1, EPI2, EPI3,
 });
+#[cfg(feature="dreier")] // three players (Dreierschafkopf)
+plain_enum_mod!(modepi, derive(Serialize_repr,), map_derive(), EPlayerIndex {
+    EPI0, EPI1, EPI2,
+});
 define_static_value!(pub SStaticEPI0, EPlayerIndex, EPlayerIndex::EPI0);
 define_static_value!(pub SStaticEPI1, EPlayerIndex, EPlayerIndex::EPI1);
 define_static_value!(pub SStaticEPI2, EPlayerIndex, EPlayerIndex::EPI2);
+#[cfg(not(feature="dreier"))]
 define_static_value!(pub SStaticEPI3, EPlayerIndex, EPlayerIndex::EPI3);
 impl fmt::Display for EPlayerIndex {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -22,6 +22,7 @@ impl fmt::Display for SStich {
 }
 
 #[test]
+#[cfg(not(feature="dreier"))]
 fn test_stich() {
     // TODO? use quicktest or similar
     {
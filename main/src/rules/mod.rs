@@ -3,7 +3,8 @@ pub mod trumpfdecider;
 #[macro_use]
 pub mod singleplay;
 pub mod rulesrufspiel;
-// TODORULES implement Hochzeit
+pub mod ruleshochzeit;
+pub mod ruleskreuzspiel;
 pub mod card_points;
 pub mod parser;
 pub mod payoutdecider;
@@ -43,6 +44,31 @@ pub struct SStoss {
     pub epi : EPlayerIndex,
 }
 
+plain_enum_mod!(modestoss, EStoss {
+    Kontra,
+    Re,
+    Sup,
+    Hirsch,
+});
+
+impl EStoss {
+    pub fn next(vecstoss: &[SStoss]) -> Option<EStoss> {
+        EStoss::checked_from_usize(vecstoss.len())
+    }
+}
+
+plain_enum_mod!(modeschneiderschwarz, ESchneiderSchwarz {
+    Schneider,
+    Schwarz,
+});
+
+// what a player's decision during game announcement tells about their hand
+plain_enum_mod!(modeannouncementdecision, EAnnouncementDecision {
+    AnnouncedRufspiel,
+    AnnouncedSolo,
+    Passed,
+});
+
 fn all_allowed_cards_within_stich_distinguish_farbe_frei (
     rules: &(impl TRules + ?Sized),
     card_first_in_stich: SCard,
@@ -110,6 +136,10 @@ impl SPayoutHint {
     pub fn lower_bound(&self) -> &Option<SPayoutInfo> {
         &self.tpln_payout.0
     }
+
+    pub fn upper_bound(&self) -> &Option<SPayoutInfo> {
+        &self.tpln_payout.1
+    }
 }
 
 pub trait TPlayerParties {
@@ -231,7 +261,57 @@ impl TPlayerParties for SPlayerParties13 {
         self.epi==epi
     }
     fn multiplier(&self, epi: EPlayerIndex) -> isize {
-        if self.is_primary_party(epi) {3} else {1}
+        if self.is_primary_party(epi) {(EPlayerIndex::SIZE-1).as_num::<isize>()} else {1}
+    }
+}
+
+#[derive(new)]
+pub struct SPlayerParties22 {
+    aepi_pri: [EPlayerIndex; 2],
+}
+
+impl TPlayerParties for SPlayerParties22 {
+    fn is_primary_party(&self, epi: EPlayerIndex) -> bool {
+        self.aepi_pri[0]==epi || self.aepi_pri[1]==epi
+    }
+    fn multiplier(&self, _epi: EPlayerIndex) -> isize {
+        1
+    }
+}
+
+// parties as far as they are known to all players: players known to play together share the same party index
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SKnownParties {
+    mapepioi_party: EnumMap<EPlayerIndex, Option<usize>>,
+}
+
+impl SKnownParties {
+    pub fn new_unknown() -> Self {
+        Self::new_from_fn(|_epi| None)
+    }
+    pub fn new_from_fn(fn_party: impl FnMut(EPlayerIndex)->Option<usize>) -> Self {
+        Self {
+            mapepioi_party: EPlayerIndex::map_from_fn(fn_party),
+        }
+    }
+    pub fn new_each_for_himself() -> Self {
+        Self::new_from_fn(|epi| Some(epi.to_usize()))
+    }
+    pub fn new_from_playerparties(playerparties: &impl TPlayerParties) -> Self {
+        Self::new_from_fn(|epi| Some(if playerparties.is_primary_party(epi) {0} else {1}))
+    }
+    pub fn new_primary_players(itepi_primary: impl Iterator<Item=EPlayerIndex>) -> Self {
+        let mut knownparties = Self::new_unknown();
+        for epi_primary in itepi_primary {
+            knownparties.mapepioi_party[epi_primary] = Some(0);
+        }
+        knownparties
+    }
+    pub fn same_party(&self, epi_lhs: EPlayerIndex, epi_rhs: EPlayerIndex) -> bool {
+        epi_lhs==epi_rhs || matches!(
+            (self.mapepioi_party[epi_lhs], self.mapepioi_party[epi_rhs]),
+            (Some(i_party_lhs), Some(i_party_rhs)) if i_party_lhs==i_party_rhs
+        )
     }
 }
 
@@ -246,6 +326,11 @@ pub trait TRules : fmt::Display + TAsRules + Sync + fmt::Debug + TRulesBoxClone
 
     fn playerindex(&self) -> Option<EPlayerIndex>;
 
+    // card called to determine the co-player (e.g. Rufsau in Rufspiel)
+    fn called_card(&self) -> Option<SCard> {
+        None
+    }
+
     fn can_be_played(&self, _hand: SFullHand) -> bool {
         true // probably, only Rufspiel is prevented in some cases
     }
@@ -382,6 +467,52 @@ pub trait TRules : fmt::Display + TAsRules + Sync + fmt::Debug + TRulesBoxClone
     fn rulespecific_ai<'rules>(&'rules self) -> Option<Box<dyn TRuleSpecificAI + 'rules>> {
         None
     }
+
+    // returns the rules resulting from one more player pushing, None if rules do not support schieben
+    fn with_schieben(&self) -> Option<Box<dyn TRules>> {
+        None
+    }
+
+    // player offering a card to find a coplayer before the game (e.g. the single trumpf in Hochzeit), None if there is no pending offer
+    fn playerindex_offering(&self) -> Option<EPlayerIndex> {
+        None
+    }
+
+    // returns the rules resulting from epi accepting the offer, None if rules do not support offers
+    fn with_offer_accepted(&self, _epi: EPlayerIndex) -> Option<Box<dyn TRules>> {
+        None
+    }
+
+    // Schneider/Schwarz announced by the playing party before the first card
+    fn announced_schneider_schwarz(&self) -> Option<ESchneiderSchwarz> {
+        None
+    }
+
+    // returns the rules resulting from announcing eschneiderschwarz, None if rules do not support announcements
+    fn with_announced_schneider_schwarz(&self, _eschneiderschwarz: ESchneiderSchwarz) -> Option<Box<dyn TRules>> {
+        None
+    }
+
+    // player whose hand is visible to everybody (e.g. Bettel Brett)
+    fn playerindex_open_hand(&self, _stichseq: &SStichSequence) -> Option<EPlayerIndex> {
+        None
+    }
+
+    // parties as far as they are known to all players after stichseq (e.g. Rufspiel: once the Rufsau is played)
+    fn known_parties(&self, _stichseq: &SStichSequence) -> SKnownParties {
+        SKnownParties::new_unknown()
+    }
+
+    // AI considers snapshots equivalent if remaining hands, current stich and SRuleStateCacheChanging coincide.
+    // Rules depending on further details of completed stichs must encode them here.
+    fn snapshot_cache_history(&self, _stichseq: &SStichSequence) -> usize {
+        0
+    }
+
+    // What epi decided during game announcement, if these rules tell (None if unknown).
+    fn announcement_decision(&self, _epi: EPlayerIndex) -> Option<EAnnouncementDecision> {
+        None
+    }
 }
 
 make_upcastable!(TAsRules, TRules);
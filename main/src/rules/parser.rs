@@ -9,6 +9,8 @@ pub fn parse_rule_description(
     (n_tarif_extra, n_tarif_ruf, n_tarif_solo): (isize, isize, isize),
     fn_player_to_epi: impl Fn(&str)->Result<EPlayerIndex, Error>,
 ) -> Result<Box<dyn TRules>, Error> {
+    use crate::rules::rulesbettel::*;
+    use crate::rules::ruleshochzeit::*;
     use crate::rules::rulesrufspiel::*;
     use crate::rules::rulessolo::*;
     use crate::rules::rulesramsch::*;
@@ -89,6 +91,27 @@ pub fn parse_rule_description(
                 }
             }
         }),
+        (&["hochzeit"], {
+            Ok(Box::new(SRulesHochzeit::new(
+                get_epi_active()?,
+                SPayoutDeciderParams::new(
+                    /*n_payout_base*/n_tarif_ruf,
+                    /*n_payout_schneider_schwarz*/n_tarif_extra,
+                    SLaufendeParams::new(
+                        /*n_payout_single_player*/n_tarif_extra,
+                        /*n_lauf_lbound*/3,
+                    ),
+                ),
+            )) as Box<dyn TRules>)
+        }),
+        (&["bettel"], {
+            Ok(Box::new(SRulesBettel::<SBettelAllAllowedCardsWithinStichNormal>::new(
+                get_epi_active()?,
+                /*i_prio*/0,
+                /*n_payout_base*/n_tarif_solo,
+                /*b_brett*/str_rules_contains(&["brett"]),
+            )) as Box<dyn TRules>)
+        }),
         (&["solo", "sticht"], make_sololike(ESoloLike::Solo)),
         (&["wenz"], make_sololike(ESoloLike::Wenz)),
         (&["geier"], make_sololike(ESoloLike::Geier)),
@@ -96,7 +119,9 @@ pub fn parse_rule_description(
             Ok(Box::new(SRulesRamsch::new(
                 /*n_price*/n_tarif_ruf,
                 VDurchmarsch::AtLeast(91), // https://www.sauspiel.de/blog/66-bei-sauspiel-wird-jetzt-mit-ramsch-gespielt
-                // TODO Jungfrau
+                /*b_jungfrau*/false, // TODO Jungfrau
+                /*on_price_stock*/None,
+                /*b_schieben*/false,
             )) as Box<dyn TRules>)
         }),
     ].into_iter()
@@ -33,9 +33,35 @@ impl TPointsToWin for SPointsToWin61 {
 pub struct SPayoutDeciderPointBased<PointsToWin> {
     pub payoutparams : SPayoutDeciderParams,
     pub pointstowin: PointsToWin,
+    #[new(default)]
+    pub oeschneiderschwarz_announced: Option<ESchneiderSchwarz>,
 }
 
 impl<PointsToWin: TPointsToWin> SPayoutDeciderPointBased<PointsToWin> {
+    pub fn with_announced_schneider_schwarz(&self, eschneiderschwarz: ESchneiderSchwarz) -> Option<Self> {
+        if self.oeschneiderschwarz_announced.is_none_or(|eschneiderschwarz_announced| eschneiderschwarz_announced < eschneiderschwarz) {
+            Some(Self {
+                oeschneiderschwarz_announced: Some(eschneiderschwarz),
+                ..self.clone()
+            })
+        } else {
+            None
+        }
+    }
+
+    fn points_to_win(&self) -> isize {
+        let n_points_to_win = self.pointstowin.points_to_win();
+        if self.oeschneiderschwarz_announced.is_some() {
+            n_points_to_win.max(91) // announced schwarz additionally requires all stichs
+        } else {
+            n_points_to_win
+        }
+    }
+
+    fn n_announced(&self) -> isize {
+        self.oeschneiderschwarz_announced.map_or(0, |eschneiderschwarz| eschneiderschwarz.to_usize().as_num::<isize>() + 1)
+    }
+
     pub fn payout<Rules>(
         &self,
         if_dbg_else!({rules}{_rules}): &Rules,
@@ -56,25 +82,27 @@ impl<PointsToWin: TPointsToWin> SPayoutDeciderPointBased<PointsToWin> {
                 .map(|(stich, _epi_winner)| card_points::points_stich(stich))
                 .sum::<isize>()
         );
-        let b_primary_party_wins = n_points_primary_party >= self.pointstowin.points_to_win();
+        let party_wins_all_stichs = |b_primary| debug_verify_eq!(
+            EPlayerIndex::values()
+                .filter(|epi| b_primary==playerparties.is_primary_party(*epi))
+                .map(|epi|  rulestatecache.changing.mapepipointstichcount[epi].n_stich)
+                .sum::<usize>()==gamefinishedstiche.get().kurzlang().cards_per_player(),
+            gamefinishedstiche.get().completed_stichs_winner_index(rules)
+                .all(|(_stich, epi_winner)| b_primary==playerparties.is_primary_party(epi_winner))
+        );
+        let b_primary_party_wins = n_points_primary_party >= self.points_to_win()
+            && (Some(ESchneiderSchwarz::Schwarz)!=self.oeschneiderschwarz_announced || party_wins_all_stichs(/*b_primary*/true));
+        let n_schneider_schwarz = if party_wins_all_stichs(b_primary_party_wins) {
+            2 // schwarz
+        } else if (b_primary_party_wins && n_points_primary_party>90) || (!b_primary_party_wins && n_points_primary_party<=30) {
+            1 // schneider
+        } else {
+            0 // "nothing", i.e. neither schneider nor schwarz
+        };
         internal_payout(
             /*n_payout_single_player*/ self.payoutparams.n_payout_base
-                + { 
-                    if debug_verify_eq!(
-                        EPlayerIndex::values()
-                            .filter(|epi| b_primary_party_wins==playerparties.is_primary_party(*epi))
-                            .map(|epi|  rulestatecache.changing.mapepipointstichcount[epi].n_stich)
-                            .sum::<usize>()==gamefinishedstiche.get().kurzlang().cards_per_player(),
-                        gamefinishedstiche.get().completed_stichs_winner_index(rules)
-                            .all(|(_stich, epi_winner)| b_primary_party_wins==playerparties.is_primary_party(epi_winner))
-                    ) {
-                        2*self.payoutparams.n_payout_schneider_schwarz // schwarz
-                    } else if (b_primary_party_wins && n_points_primary_party>90) || (!b_primary_party_wins && n_points_primary_party<=30) {
-                        self.payoutparams.n_payout_schneider_schwarz // schneider
-                    } else {
-                        0 // "nothing", i.e. neither schneider nor schwarz
-                    }
-                }
+                // announced schneider/schwarz counts as reached (or lost) and is paid once more for the announcement
+                + (n_schneider_schwarz.max(self.n_announced()) + self.n_announced()) * self.payoutparams.n_payout_schneider_schwarz
                 + self.payoutparams.laufendeparams.payout_laufende::<Rules, _>(rulestatecache, gamefinishedstiche, playerparties),
             playerparties,
             b_primary_party_wins,
@@ -101,7 +129,8 @@ impl<PointsToWin: TPointsToWin> SPayoutDeciderPointBased<PointsToWin> {
         );
         let internal_payouthints = |b_primary_party_wins| {
             internal_payout(
-                /*n_payout_single_player*/ self.payoutparams.n_payout_base,
+                /*n_payout_single_player*/ self.payoutparams.n_payout_base
+                    + 2 * self.n_announced() * self.payoutparams.n_payout_schneider_schwarz,
                 playerparties,
                 b_primary_party_wins,
             )
@@ -110,9 +139,14 @@ impl<PointsToWin: TPointsToWin> SPayoutDeciderPointBased<PointsToWin> {
                      tpl_flip_if(0<*n_payout, (None, Some(*n_payout)))
                 })
         };
-        if /*b_primary_party_wins*/ mapbn_points[/*b_primary*/true] >= self.pointstowin.points_to_win() {
+        let b_schwarz_announced = Some(ESchneiderSchwarz::Schwarz)==self.oeschneiderschwarz_announced;
+        if /*b_primary_party_wins*/ !b_schwarz_announced && mapbn_points[/*b_primary*/true] >= self.points_to_win() {
             internal_payouthints(/*b_primary_party_wins*/true)
-        } else if mapbn_points[/*b_primary*/false] > 120-self.pointstowin.points_to_win() {
+        } else if mapbn_points[/*b_primary*/false] > 120-self.points_to_win()
+            || (b_schwarz_announced && EPlayerIndex::values().any(|epi|
+                !playerparties.is_primary_party(epi) && 0<rulestatecache.changing.mapepipointstichcount[epi].n_stich
+            ))
+        {
             internal_payouthints(/*b_primary_party_wins*/false)
         } else {
             EPlayerIndex::map_from_fn(|_epi| (None, None))
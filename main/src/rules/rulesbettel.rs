@@ -1,3 +1,4 @@
+use crate::ai::rulespecific::aibettel::*;
 use crate::primitives::*;
 use crate::rules::{
     payoutdecider::internal_payout, rulessolo::TPayoutDecider, trumpfdecider::*, *,
@@ -6,23 +7,31 @@ use crate::util::*;
 use std::marker::PhantomData;
 
 pub trait TBettelAllAllowedCardsWithinStich : Sync + 'static + Clone + fmt::Debug + Send {
-    fn all_allowed_cards_within_stich(rulesbettel: &SRulesBettel<Self>, stichseq: &SStichSequence, hand: &SHand) -> SHandVector;
+    fn all_allowed_cards_within_stich<TrumpfDecider: TTrumpfDecider>(rulesbettel: &SRulesBettel<Self, TrumpfDecider>, stichseq: &SStichSequence, hand: &SHand) -> SHandVector;
 }
 
+pub type STrumpfDeciderBettel = STrumpfDeciderNoTrumpf<SCompareFarbcardsBettel>;
+pub type STrumpfDeciderBettelOberUnter = STrumpfDeciderSchlag<
+    SStaticSchlagOber, STrumpfDeciderSchlag<
+    SStaticSchlagUnter,
+    STrumpfDeciderBettel>>;
+
 #[derive(Clone, Debug)]
-pub struct SRulesBettel<BettelAllAllowedCardsWithinStich> {
+pub struct SRulesBettel<BettelAllAllowedCardsWithinStich, TrumpfDecider=STrumpfDeciderBettel> {
     epi : EPlayerIndex,
     i_prio : isize,
     payoutdecider : SPayoutDeciderBettel,
-    phantom : PhantomData<BettelAllAllowedCardsWithinStich>,
+    b_brett : bool, // Bettel Brett: hand is visible to everybody after the first stich
+    phantom : PhantomData<(BettelAllAllowedCardsWithinStich, TrumpfDecider)>,
 }
 
-impl<BettelAllAllowedCardsWithinStich: TBettelAllAllowedCardsWithinStich> SRulesBettel<BettelAllAllowedCardsWithinStich> {
-    pub fn new(epi: EPlayerIndex, i_prio: isize, n_payout_base: isize) -> SRulesBettel<BettelAllAllowedCardsWithinStich> {
+impl<BettelAllAllowedCardsWithinStich: TBettelAllAllowedCardsWithinStich, TrumpfDecider: TTrumpfDecider> SRulesBettel<BettelAllAllowedCardsWithinStich, TrumpfDecider> {
+    pub fn new(epi: EPlayerIndex, i_prio: isize, n_payout_base: isize, b_brett: bool) -> SRulesBettel<BettelAllAllowedCardsWithinStich, TrumpfDecider> {
         SRulesBettel{
             epi,
             i_prio,
             payoutdecider: SPayoutDeciderBettel{n_payout_base},
+            b_brett,
             phantom: PhantomData,
         }
     }
@@ -31,13 +40,13 @@ impl<BettelAllAllowedCardsWithinStich: TBettelAllAllowedCardsWithinStich> SRules
     }
 }
 
-impl<BettelAllAllowedCardsWithinStich: TBettelAllAllowedCardsWithinStich> fmt::Display for SRulesBettel<BettelAllAllowedCardsWithinStich> {
+impl<BettelAllAllowedCardsWithinStich: TBettelAllAllowedCardsWithinStich, TrumpfDecider: TTrumpfDecider> fmt::Display for SRulesBettel<BettelAllAllowedCardsWithinStich, TrumpfDecider> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Bettel von {}", self.epi)
+        write!(f, "Bettel{} von {}", if self.b_brett {" Brett"} else {""}, self.epi)
     }
 }
 
-impl<BettelAllAllowedCardsWithinStich: TBettelAllAllowedCardsWithinStich> TActivelyPlayableRules for SRulesBettel<BettelAllAllowedCardsWithinStich> {
+impl<BettelAllAllowedCardsWithinStich: TBettelAllAllowedCardsWithinStich, TrumpfDecider: TTrumpfDecider> TActivelyPlayableRules for SRulesBettel<BettelAllAllowedCardsWithinStich, TrumpfDecider> {
     fn priority(&self) -> VGameAnnouncementPriority {
         VGameAnnouncementPriority::SoloLike(VGameAnnouncementPrioritySoloLike::SoloSimple(self.i_prio))
     }
@@ -102,7 +111,7 @@ pub struct SBettelAllAllowedCardsWithinStichNormal {}
 pub struct SBettelAllAllowedCardsWithinStichStichzwang {}
 
 impl TBettelAllAllowedCardsWithinStich for SBettelAllAllowedCardsWithinStichNormal {
-    fn all_allowed_cards_within_stich(rulesbettel: &SRulesBettel<Self>, stichseq: &SStichSequence, hand: &SHand) -> SHandVector {
+    fn all_allowed_cards_within_stich<TrumpfDecider: TTrumpfDecider>(rulesbettel: &SRulesBettel<Self, TrumpfDecider>, stichseq: &SStichSequence, hand: &SHand) -> SHandVector {
         assert!(!stichseq.current_stich().is_empty());
         all_allowed_cards_within_stich_distinguish_farbe_frei(
             rulesbettel,
@@ -113,7 +122,7 @@ impl TBettelAllAllowedCardsWithinStich for SBettelAllAllowedCardsWithinStichNorm
     }
 }
 impl TBettelAllAllowedCardsWithinStich for SBettelAllAllowedCardsWithinStichStichzwang {
-    fn all_allowed_cards_within_stich(rulesbettel: &SRulesBettel<Self>, stichseq: &SStichSequence, hand: &SHand) -> SHandVector {
+    fn all_allowed_cards_within_stich<TrumpfDecider: TTrumpfDecider>(rulesbettel: &SRulesBettel<Self, TrumpfDecider>, stichseq: &SStichSequence, hand: &SHand) -> SHandVector {
         let stich = stichseq.current_stich();
         let card_highest = stich[rulesbettel.preliminary_winner_index(stich)];
         assert!(!stichseq.current_stich().is_empty());
@@ -124,7 +133,7 @@ impl TBettelAllAllowedCardsWithinStich for SBettelAllAllowedCardsWithinStichStic
             /*fn_farbe_not_frei*/|veccard_same_farbe| {
                 let veccard_allowed_higher_than_current_best = veccard_same_farbe.iter().copied()
                     .filter(|card| 
-                        match SCompareFarbcardsBettel::compare_farbcards(card_highest, *card) {
+                        match unwrap!(rulesbettel.compare_cards(card_highest, *card)) {
                             Ordering::Less => true,
                             Ordering::Equal => panic!("Unexpected comparison result in Bettel"),
                             Ordering::Greater => false,
@@ -141,17 +150,25 @@ impl TBettelAllAllowedCardsWithinStich for SBettelAllAllowedCardsWithinStichStic
     }
 }
 
-impl<BettelAllAllowedCardsWithinStich: TBettelAllAllowedCardsWithinStich> TRulesNoObj for SRulesBettel<BettelAllAllowedCardsWithinStich> {
-    impl_rules_trumpf_noobj!(STrumpfDeciderNoTrumpf<SCompareFarbcardsBettel>);
+impl<BettelAllAllowedCardsWithinStich: TBettelAllAllowedCardsWithinStich, TrumpfDecider: TTrumpfDecider> TRulesNoObj for SRulesBettel<BettelAllAllowedCardsWithinStich, TrumpfDecider> {
+    impl_rules_trumpf_noobj!(TrumpfDecider);
 }
 
-impl<BettelAllAllowedCardsWithinStich: TBettelAllAllowedCardsWithinStich> TRules for SRulesBettel<BettelAllAllowedCardsWithinStich> {
+impl<BettelAllAllowedCardsWithinStich: TBettelAllAllowedCardsWithinStich, TrumpfDecider: TTrumpfDecider> TRules for SRulesBettel<BettelAllAllowedCardsWithinStich, TrumpfDecider> {
     impl_rules_trumpf!();
     impl_single_play!();
 
     fn all_allowed_cards_within_stich(&self, stichseq: &SStichSequence, hand: &SHand) -> SHandVector {
         BettelAllAllowedCardsWithinStich::all_allowed_cards_within_stich(self, stichseq, hand)
     }
+
+    fn playerindex_open_hand(&self, stichseq: &SStichSequence) -> Option<EPlayerIndex> {
+        if_then_some!(self.b_brett && !stichseq.completed_stichs().is_empty(), self.epi)
+    }
+
+    fn rulespecific_ai<'rules>(&'rules self) -> Option<Box<dyn TRuleSpecificAI + 'rules>> {
+        Some(Box::new(SAIBettel::new(self, self.internal_playerindex())))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -1,6 +1,6 @@
 use crate::primitives::*;
 use crate::rules::{
-    payoutdecider::*, rulesbettel::*, rulesramsch::*, rulesrufspiel::*, rulessolo::*, *,
+    payoutdecider::*, rulesbettel::*, ruleshochzeit::*, ruleskreuzspiel::*, rulesramsch::*, rulesrufspiel::*, rulessolo::*, trumpfdecider::TTrumpfDecider, *,
 };
 use crate::util::*;
 use std::{fs::File, io::prelude::*, path::Path};
@@ -30,6 +30,17 @@ impl SRuleGroup {
         }
     }
 
+    pub fn without_nothing(&self) -> Option<SRuleGroup> {
+        let vecorules_active = self.vecorules.iter()
+            .filter(|orules| orules.is_some())
+            .cloned()
+            .collect::<Vec<_>>();
+        if_then_some!(!vecorules_active.is_empty(), SRuleGroup {
+            str_name: self.str_name.clone(),
+            vecorules: vecorules_active,
+        })
+    }
+
     pub fn allowed_rules<'retval, 'hand : 'retval, 'rules : 'retval>(&'rules self, hand: SFullHand<'hand>) -> impl Clone + Iterator<Item=Option<&'rules dyn TActivelyPlayableRules>> + 'retval {
         self.vecorules.iter().map(|orules| orules.as_ref().map(|rules| rules.as_ref()))
             .filter(move |orules| orules.map_or(true, |rules| rules.can_be_played(hand)))
@@ -48,9 +59,17 @@ pub enum EDoublingScope {
     GamesAndStock,
 }
 
+#[derive(Debug, Clone)]
+pub enum EStossTime {
+    BeforeFirstCard, // nobody may give stoss once the first card is played
+    BeforeOwnFirstCard, // each player may give stoss until he plays his first card
+}
+
 #[derive(Clone, new, Debug)]
 pub struct SStossParams {
     pub n_stoss_max : usize,
+    pub estosstime : EStossTime,
+    pub b_announce_schneider_schwarz : bool, // playing party may announce Schneider/Schwarz before the first card
 }
 
 #[derive(new, Debug, Clone)]
@@ -60,6 +79,7 @@ pub struct SRuleSet {
     pub oedoublingscope : Option<EDoublingScope>,
     pub ostossparams : Option<SStossParams>,
     pub ekurzlang : EKurzLang,
+    pub b_der_alte_muss : bool,
 }
 
 pub fn allowed_rules<'retval, 'hand : 'retval, 'rules : 'retval>(vecrulegroup: &'rules [SRuleGroup], hand: SFullHand<'hand>) -> impl Clone + Iterator<Item=Option<&'rules (dyn TActivelyPlayableRules + 'rules)>> + 'retval {
@@ -85,14 +105,38 @@ impl SRuleSet {
             info!("SRuleSet: Did not find {}. Falling back to {}.", str_not_found, str_fallback);
             read_int(&tomltbl, str_fallback)
         };
-        // TODORULES "Der Alte muss"
-        // TODORULES Kreuzspiel as alternative to Ramsch
-        let stockorramsch = match (tomltbl.get("ramsch"), tomltbl.get("stock")) {
-            (Some(_), Some(_)) => {
-                // TODORULES Better alternatives? Allow stock *and* ramsch at the same time?
-                bail!("Currently, having both Ramsch and Stock is not supported.")
+        // the number of players is fixed at compile time (see feature dreier), so a ruleset can only state which one it expects
+        if let Some(n_players) = tomltbl.get("players") {
+            if Some(EPlayerIndex::SIZE.as_num::<i64>())!=n_players.as_integer() {
+                bail!("Ruleset is meant for {} players, but this build supports {} players.", n_players, EPlayerIndex::SIZE);
+            }
+        }
+        let ekurzlang_default = if EKurzLang::Lang.can_be_dealt() {EKurzLang::Lang} else {EKurzLang::Kurz};
+        let ekurzlang = match tomltbl.get("deck").and_then(|tomlval_kurzlang| tomlval_kurzlang.as_str()) {
+            Some("kurz") => EKurzLang::Kurz,
+            Some("lang") => EKurzLang::Lang,
+            None => ekurzlang_default,
+            Some(str_kurzlang) => {
+                info!("SRuleSet: {} is not a valid value for 'deck' (supported values: kurz, lang). Defaulting to {:?}", str_kurzlang, ekurzlang_default);
+                ekurzlang_default
             },
-            (Some(val_ramsch), None) => {
+        };
+        if !ekurzlang.can_be_dealt() {
+            bail!("Deck {:?} cannot be dealt to {} players.", ekurzlang, EPlayerIndex::SIZE);
+        }
+        for str_rule_name_file in ["rufspiel", "hochzeit"] {
+            if tomltbl.get(str_rule_name_file).is_some() && 4!=EPlayerIndex::SIZE {
+                bail!("{} requires four players.", str_rule_name_file);
+            }
+        }
+        let stockorramsch = match (tomltbl.get("ramsch"), tomltbl.get("kreuzspiel"), tomltbl.get("stock")) {
+            (Some(_), Some(_), _) => {
+                bail!("Ramsch and Kreuzspiel are mutually exclusive.")
+            },
+            (None, Some(_), Some(_)) => {
+                bail!("Currently, having both Kreuzspiel and Stock is not supported.")
+            },
+            (Some(val_ramsch), None, oval_stock) => {
                 let durchmarsch = (match val_ramsch.get("durchmarsch") {
                     None => Ok(VDurchmarsch::None),
                     Some(&toml::Value::String(ref str_durchmarsch)) if "all"==str_durchmarsch => {
@@ -103,16 +147,40 @@ impl SRuleSet {
                     },
                     _ => bail!("Invalid value for ramsch.durchmarsch. \"All\" or a number in [61; 120] is supported.")
                 } as Result<_, Error>)?;
+                // stock is filled by the loser of the ramsch, and won by the next game
+                let on_price_stock = match oval_stock {
+                    None => None,
+                    Some(val_stock) => Some(read_int(val_stock, "price").or_else(|_err| fallback("stock.price", "base-price"))?.as_num()),
+                };
                 read_int(val_ramsch, "price").map(|n_price|
                     VStockOrT::OrT(Box::new(
-                        SRulesRamsch::new(n_price.as_num(), durchmarsch)
+                        SRulesRamsch::new(
+                            n_price.as_num(),
+                            durchmarsch,
+                            /*b_jungfrau*/Some(true)==val_ramsch.get("jungfrau").and_then(|tomlval| tomlval.as_bool()),
+                            on_price_stock,
+                            /*b_schieben*/Some(true)==val_ramsch.get("schieben").and_then(|tomlval| tomlval.as_bool()),
+                        )
+                    ) as Box<dyn TRules>)
+                )
+            },
+            (None, Some(val_kreuzspiel), None) => {
+                let ekreuzspielparties = match val_kreuzspiel.get("parties").and_then(|tomlval| tomlval.as_str()) {
+                    None | Some("single") => EKreuzspielParties::EachForHimself,
+                    Some("alte") if 4==EPlayerIndex::SIZE => EKreuzspielParties::Alte,
+                    Some("alte") => bail!("kreuzspiel.parties=\"alte\" requires four players."),
+                    Some(str_parties) => bail!("Invalid value for kreuzspiel.parties: {}. \"single\" or \"alte\" is supported.", str_parties),
+                };
+                read_int(val_kreuzspiel, "price").or_else(|_err| fallback("kreuzspiel.price", "base-price")).map(|n_price|
+                    VStockOrT::OrT(Box::new(
+                        SRulesKreuzspiel::new(n_price.as_num(), ekreuzspielparties)
                     ) as Box<dyn TRules>)
                 )
             },
-            (None, Some(val_stock)) => {
+            (None, None, Some(val_stock)) => {
                 read_int(val_stock, "price").or_else(|_err| fallback("stock.price", "base-price")).map(|n_price| VStockOrT::Stock(n_price.as_num()))
             },
-            (None, None) => {
+            (None, None, None) => {
                 Ok(VStockOrT::Stock(0)) // represent "no stock" by using a zero stock payment
             }
         }?;
@@ -120,9 +188,24 @@ impl SRuleSet {
         for epi in EPlayerIndex::values() {
             let vecrulegroup = &mut avecrulegroup[epi];
             macro_rules! create_rulegroup {($str_rule_name_file: expr, $str_base_price_fallback: expr, $str_group_name: expr, $fn_rules: expr) => {
-                if let Some(tomlval_game) = tomltbl.get($str_rule_name_file) {
+                create_rulegroup!($str_rule_name_file, /*otplstrb_variant*/None, $str_base_price_fallback, $str_group_name, $fn_rules)
+            }; ($str_rule_name_file: expr, $otplstrb_variant: expr, $str_base_price_fallback: expr, $str_group_name: expr, $fn_rules: expr) => {{
+                // variants (e.g. tout) can be switched on and off and may have their own price
+                let otplstrb_variant : Option<(&str, bool)> = $otplstrb_variant;
+                if let Some(tomlval_game) = tomltbl.get($str_rule_name_file)
+                    .filter(|tomlval_game| otplstrb_variant.map_or(true, |(str_variant, b_variant_default)| {
+                        tomlval_game.get(str_variant)
+                            .and_then(|tomlval| tomlval.as_bool())
+                            .unwrap_or(b_variant_default)
+                    }))
+                {
                     let n_payout_extra = read_int(tomlval_game, "extra").or_else(|_err| fallback(&format!("{}.extra", $str_rule_name_file), "base-price"))?;
-                    let n_payout_base = read_int(tomlval_game, "price").or_else(|_err| fallback(&format!("{}.price", $str_rule_name_file), $str_base_price_fallback))?;
+                    let n_payout_base = otplstrb_variant
+                        .and_then(|(str_variant, _b_variant_default)| read_int(tomlval_game, &format!("{}-price", str_variant)).ok())
+                        .map_or_else(
+                            || read_int(tomlval_game, "price").or_else(|_err| fallback(&format!("{}.price", $str_rule_name_file), $str_base_price_fallback)),
+                            Ok,
+                        )?;
                     let n_lauf_lbound = read_int(tomlval_game, "lauf-min").or_else(|_err| fallback(&format!("{}.lauf-min", $str_rule_name_file), "lauf-min"))?;
                     Ok(vecrulegroup.push(SRuleGroup{
                         str_name: $str_group_name.to_string(),
@@ -138,30 +221,57 @@ impl SRuleSet {
                 } else {
                     Ok(())
                 }
-            }}
-            macro_rules! create_rulegroup_sololike {($str_rule_name_file: expr, $str_group_name: expr, $fn_rules: expr) => {
-                create_rulegroup!($str_rule_name_file, "solo-price", $str_group_name, $fn_rules)
+            }}}
+            macro_rules! create_rulegroup_sololike {($str_rule_name_file: expr, $otplstrb_variant: expr, $str_group_name: expr, $fn_rules: expr) => {
+                create_rulegroup!($str_rule_name_file, $otplstrb_variant, "solo-price", $str_group_name, $fn_rules)
             }}
             vecrulegroup.push(SRuleGroup{
                 str_name: "Nothing".to_string(),
                 vecorules: vec![None],
             });
+            let read_rufspiel_bool = |str_key: &str, b_default: bool| {
+                tomltbl.get("rufspiel")
+                    .and_then(|tomlval_rufspiel| tomlval_rufspiel.get(str_key))
+                    .and_then(|tomlval| tomlval.as_bool())
+                    .unwrap_or(b_default)
+            };
+            let b_davonlaufen = read_rufspiel_bool("davonlaufen", /*b_default*/true);
+            let veceschlag_ruf = if read_rufspiel_bool("ruf-zehn", /*b_default*/false) {
+                vec![ESchlag::Ass, ESchlag::Zehn]
+            } else {
+                vec![ESchlag::Ass]
+            };
             create_rulegroup!(
                 "rufspiel",
                 "base-price",
                 "Rufspiel", 
                 |payoutparams: SPayoutDeciderParams| {
-                    EFarbe::values()
-                        .filter(|efarbe| EFarbe::Herz!=*efarbe)
-                        .map(|efarbe| Some(Box::new(SRulesRufspiel::new(
+                    veceschlag_ruf.iter()
+                        .flat_map(|&eschlag_ruf| EFarbe::values()
+                            .filter(|efarbe| EFarbe::Herz!=*efarbe)
+                            .map(move |efarbe| (eschlag_ruf, efarbe))
+                        )
+                        .map(|(eschlag_ruf, efarbe)| Some(Box::new(SRulesRufspiel::new_with_params(
                             epi,
                             efarbe,
                             payoutparams.clone(),
+                            SRufspielParams::new(eschlag_ruf, b_davonlaufen),
                         )) as Box<dyn TActivelyPlayableRules>))
                         .collect()
                 }
             )?;
-            macro_rules! read_sololike {($payoutdecider: ty, $fn_prio: expr, $str_rulename_suffix: expr) => {{
+            create_rulegroup!(
+                "hochzeit",
+                "base-price",
+                "Hochzeit",
+                |payoutparams: SPayoutDeciderParams| {
+                    vec![Some(Box::new(SRulesHochzeit::new(
+                        epi,
+                        payoutparams,
+                    )) as Box<dyn TActivelyPlayableRules>)]
+                }
+            )?;
+            macro_rules! read_sololike {($payoutdecider: ty, $fn_prio: expr, $otplstrb_variant: expr, $str_rulename_suffix: expr) => {{
                 type PayoutDecider = $payoutdecider;
                 let internal_rulename = |str_rulename| {
                     format!("{}{}", str_rulename, $str_rulename_suffix)
@@ -182,26 +292,31 @@ impl SRuleSet {
                 }}
                 create_rulegroup_sololike!(
                     "solo",
+                    $otplstrb_variant,
                     &internal_rulename("Solo"),
                     vecrules!(EFarbe::values(), ESoloLike::Solo, $fn_prio(0))
                 )?;
                 create_rulegroup_sololike!(
                     "wenz",
+                    $otplstrb_variant,
                     &internal_rulename("Wenz"),
                     vecrules!(std::iter::once(None), ESoloLike::Wenz, $fn_prio(-1))
                 )?;
                 create_rulegroup_sololike!(
                     "farbwenz",
+                    $otplstrb_variant,
                     &internal_rulename("Farbwenz"),
                     vecrules!(EFarbe::values(), ESoloLike::Wenz, $fn_prio(-2))
                 )?;
                 create_rulegroup_sololike!(
                     "geier",
+                    $otplstrb_variant,
                     &internal_rulename("Geier"),
                     vecrules!(std::iter::once(None), ESoloLike::Geier, $fn_prio(-3))
                 )?;
                 create_rulegroup_sololike!(
                     "farbgeier",
+                    $otplstrb_variant,
                     &internal_rulename("Farbgeier"),
                     vecrules!(EFarbe::values(), ESoloLike::Geier, $fn_prio(-4))
                 )?;
@@ -218,21 +333,33 @@ impl SRuleSet {
                     info!("SRuleSet: steigern.steps not specified");
                     10
                 };
-                read_sololike!(SPayoutDeciderPointBased<VGameAnnouncementPrioritySoloLike>, |_i_prio| VGameAnnouncementPrioritySoloLike::SoloSteigern{n_points_to_win: 61, n_step}, "");
+                read_sololike!(SPayoutDeciderPointBased<VGameAnnouncementPrioritySoloLike>, |_i_prio| VGameAnnouncementPrioritySoloLike::SoloSteigern{n_points_to_win: 61, n_step}, /*otplstrb_variant*/None, "");
             } else {
-                read_sololike!(SPayoutDeciderPointBased<VGameAnnouncementPrioritySoloLike>, VGameAnnouncementPrioritySoloLike::SoloSimple, "");
+                read_sololike!(SPayoutDeciderPointBased<VGameAnnouncementPrioritySoloLike>, VGameAnnouncementPrioritySoloLike::SoloSimple, /*otplstrb_variant*/None, "");
+            }
+            read_sololike!(SPayoutDeciderTout, |x|x, Some(("tout", /*b_variant_default*/true)), " Tout");
+            for (str_rule_name_file, esololike, str_group_name, b_sie_default) in [
+                ("solo", ESoloLike::Solo, "Sie", true),
+                ("wenz", ESoloLike::Wenz, "Wenz-Sie", false),
+                ("geier", ESoloLike::Geier, "Geier-Sie", false),
+            ] {
+                create_rulegroup_sololike!(
+                    str_rule_name_file,
+                    Some(("sie", b_sie_default)),
+                    str_group_name,
+                    &|payoutparams| vec![Some(sololike(
+                        epi,
+                        /*oefarbe*/None,
+                        esololike,
+                        SPayoutDeciderSie::new(payoutparams, esololike),
+                    ))]
+                )?;
+            }
+            for str_rule_name_file in ["farbwenz", "farbgeier"] {
+                if tomltbl.get(str_rule_name_file).and_then(|tomlval_game| tomlval_game.get("sie")).is_some() {
+                    info!("SRuleSet: {}.sie is ignored since Sie does not depend on a trumpf farbe. Use wenz.sie resp. geier.sie instead.", str_rule_name_file);
+                }
             }
-            read_sololike!(SPayoutDeciderTout, |x|x, " Tout");
-            create_rulegroup_sololike!(
-                "solo",
-                "Sie",
-                &|payoutparams| vec![Some(sololike(
-                    epi,
-                    /*oefarbe*/None,
-                    ESoloLike::Solo,
-                    SPayoutDeciderSie::new(payoutparams),
-                ))]
-            )?;
             { // Bettel
                 let str_rule_name_file = "bettel";
                 if let Some(tomlval_bettel) = tomltbl.get(str_rule_name_file) {
@@ -240,20 +367,46 @@ impl SRuleSet {
                         .or_else(|_err| 
                             fallback(&format!("{}.price", str_rule_name_file), /*str_base_price_fallback*/"base-price")
                         )?;
-                    fn push_bettel<BettelAllAllowedCardsWithinStich: TBettelAllAllowedCardsWithinStich>(vecrulegroup: &mut Vec<SRuleGroup>, epi: EPlayerIndex, n_payout_base: isize) {
+                    let read_bettel_bool = |str_key: &str| {
+                        Some(true) == tomlval_bettel.get(str_key).and_then(|tomlval| tomlval.as_bool())
+                    };
+                    let on_payout_brett = if read_bettel_bool("brett") {
+                        Some(read_int(tomlval_bettel, "brett-price").or_else(|_err| -> Result<_, Error> {
+                            info!("SRuleSet: Did not find bettel.brett-price. Falling back to bettel.price.");
+                            Ok(n_payout_base)
+                        })?)
+                    } else {
+                        None
+                    };
+                    fn push_bettel<BettelAllAllowedCardsWithinStich: TBettelAllAllowedCardsWithinStich, TrumpfDecider: TTrumpfDecider>(vecrulegroup: &mut Vec<SRuleGroup>, epi: EPlayerIndex, n_payout_base: isize, on_payout_brett: Option<isize>) {
                         vecrulegroup.push(SRuleGroup{
                             str_name: "Bettel".to_string(),
-                            vecorules: vec![Some(Box::new(SRulesBettel::<BettelAllAllowedCardsWithinStich>::new(
+                            vecorules: vec![Some(Box::new(SRulesBettel::<BettelAllAllowedCardsWithinStich, TrumpfDecider>::new(
                                 epi,
                                 /*i_prio, large negative number to make less important than any sololike*/-999_999,
-                                n_payout_base.as_num::<isize>(),
+                                n_payout_base,
+                                /*b_brett*/false,
                             )) as Box<dyn TActivelyPlayableRules>)],
                         });
+                        if let Some(n_payout_brett) = on_payout_brett {
+                            vecrulegroup.push(SRuleGroup{
+                                str_name: "Bettel Brett".to_string(),
+                                vecorules: vec![Some(Box::new(SRulesBettel::<BettelAllAllowedCardsWithinStich, TrumpfDecider>::new(
+                                    epi,
+                                    /*i_prio, more important than Bettel*/-999_998,
+                                    n_payout_brett,
+                                    /*b_brett*/true,
+                                )) as Box<dyn TActivelyPlayableRules>)],
+                            });
+                        }
                     }
-                    if Some(true) == tomlval_bettel.get("stichzwang").and_then(|tomlval| tomlval.as_bool()) {
-                        push_bettel::<SBettelAllAllowedCardsWithinStichStichzwang>(vecrulegroup, epi, n_payout_base.as_num::<isize>());
-                    } else {
-                        push_bettel::<SBettelAllAllowedCardsWithinStichNormal>(vecrulegroup, epi, n_payout_base.as_num::<isize>());
+                    let n_payout_base = n_payout_base.as_num::<isize>();
+                    let on_payout_brett = on_payout_brett.map(|n_payout_brett| n_payout_brett.as_num::<isize>());
+                    match (read_bettel_bool("stichzwang"), read_bettel_bool("ober-unter-trumpf")) {
+                        (true, true) => push_bettel::<SBettelAllAllowedCardsWithinStichStichzwang, STrumpfDeciderBettelOberUnter>(vecrulegroup, epi, n_payout_base, on_payout_brett),
+                        (true, false) => push_bettel::<SBettelAllAllowedCardsWithinStichStichzwang, STrumpfDeciderBettel>(vecrulegroup, epi, n_payout_base, on_payout_brett),
+                        (false, true) => push_bettel::<SBettelAllAllowedCardsWithinStichNormal, STrumpfDeciderBettelOberUnter>(vecrulegroup, epi, n_payout_base, on_payout_brett),
+                        (false, false) => push_bettel::<SBettelAllAllowedCardsWithinStichNormal, STrumpfDeciderBettel>(vecrulegroup, epi, n_payout_base, on_payout_brett),
                     }
                 }
             }
@@ -277,7 +430,7 @@ impl SRuleSet {
                 }
             }),
             tomltbl.get("stoss").map(|tomlval_stoss| {
-                let n_stoss_max_default = 4;
+                let n_stoss_max_default = EStoss::SIZE;
                 SStossParams::new(
                     tomlval_stoss.get("max")
                         .and_then(|tomlval| tomlval.as_integer())
@@ -285,20 +438,28 @@ impl SRuleSet {
                             if n_stoss_max<=0 {
                                 info!("SRuleSet: stoss.max less than 0. Defaulting to {}.", n_stoss_max_default);
                                 n_stoss_max_default
+                            } else if n_stoss_max.as_num::<usize>()>EStoss::SIZE {
+                                info!("SRuleSet: stoss.max greater than {}. Defaulting to {}.", EStoss::SIZE, n_stoss_max_default);
+                                n_stoss_max_default
                             } else {
                                 n_stoss_max.as_num::<usize>()
                             }
-                        })
+                        }),
+                    match tomlval_stoss.get("until").and_then(|tomlval| tomlval.as_str()) {
+                        None | Some("first-card") => EStossTime::BeforeFirstCard,
+                        Some("own-first-card") => EStossTime::BeforeOwnFirstCard,
+                        Some(str_stosstime) => {
+                            info!("SRuleSet: {} is not a valid value for 'stoss.until' (supported values: first-card, own-first-card). Defaulting to 'first-card'", str_stosstime);
+                            EStossTime::BeforeFirstCard
+                        },
+                    },
+                    /*b_announce_schneider_schwarz*/tomlval_stoss.get("announce-schneider-schwarz")
+                        .and_then(|tomlval| tomlval.as_bool())
+                        .unwrap_or(false),
                 )
             }),
-            match tomltbl.get("deck").and_then(|tomlval_kurzlang| tomlval_kurzlang.as_str()) {
-                Some("kurz") => EKurzLang::Kurz,
-                None | Some("lang") => EKurzLang::Lang,
-                Some(str_kurzlang) => {
-                    info!("SRuleSet: {} is not a valid value for 'deck' (supported values: kurz, lang). Defaulting to 'lang'", str_kurzlang);
-                    EKurzLang::Lang
-                },
-            },
+            ekurzlang,
+            /*b_der_alte_muss*/tomltbl.get("der-alte-muss").is_some(),
         ))
     }
 
@@ -0,0 +1,158 @@
+use crate::primitives::*;
+use crate::rules::{payoutdecider::*, rulesrufspiel::STrumpfDeciderRufspiel, trumpfdecider::*, *};
+use crate::util::*;
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct SRulesHochzeit {
+    epi : EPlayerIndex,
+    oepi_coplayer : Option<EPlayerIndex>, // player who accepted the offered trumpf before the game
+    payoutdecider: SPayoutDeciderPointBased<SPointsToWin61>,
+}
+
+impl fmt::Display for SRulesHochzeit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(epi_coplayer) = self.oepi_coplayer {
+            write!(f, "Hochzeit mit {}", epi_coplayer)
+        } else {
+            write!(f, "Hochzeit")
+        }
+    }
+}
+
+impl SRulesHochzeit {
+    pub fn new(epi: EPlayerIndex, payoutparams: SPayoutDeciderParams) -> SRulesHochzeit {
+        SRulesHochzeit {
+            epi,
+            oepi_coplayer: None,
+            payoutdecider: SPayoutDeciderPointBased::new(payoutparams, SPointsToWin61{}),
+        }
+    }
+
+    fn coplayer(&self, stichseq: &SStichSequence) -> Option<EPlayerIndex> {
+        // if nobody accepted the offer, coplayer is whoever wins the first stich not won by the player offering Hochzeit
+        self.oepi_coplayer.or_else(|| stichseq.completed_stichs_winner_index(self)
+            .map(|(_stich, epi_winner)| epi_winner)
+            .find(|epi_winner| *epi_winner!=self.epi)
+        )
+    }
+}
+
+impl TActivelyPlayableRules for SRulesHochzeit {
+    fn priority(&self) -> VGameAnnouncementPriority {
+        VGameAnnouncementPriority::RufspielLike
+    }
+}
+
+impl TRulesNoObj for SRulesHochzeit {
+    impl_rules_trumpf_noobj!(STrumpfDeciderRufspiel);
+}
+
+impl TRules for SRulesHochzeit {
+    impl_rules_trumpf!();
+
+    fn can_be_played(&self, hand: SFullHand) -> bool {
+        // after an accepted offer, the single trumpf belongs to the coplayer
+        (if self.oepi_coplayer.is_some() {0} else {1})==hand.get().iter()
+            .filter(|&card| self.trumpforfarbe(*card).is_trumpf())
+            .count()
+    }
+
+    fn playerindex(&self) -> Option<EPlayerIndex> {
+        Some(self.epi)
+    }
+
+    fn playerindex_offering(&self) -> Option<EPlayerIndex> {
+        if_then_some!(self.oepi_coplayer.is_none(), self.epi)
+    }
+
+    fn with_offer_accepted(&self, epi: EPlayerIndex) -> Option<Box<dyn TRules>> {
+        assert!(self.oepi_coplayer.is_none());
+        assert_ne!(epi, self.epi);
+        Some(Box::new(SRulesHochzeit {
+            oepi_coplayer: Some(epi),
+            ..self.clone()
+        }))
+    }
+
+    fn announced_schneider_schwarz(&self) -> Option<ESchneiderSchwarz> {
+        self.payoutdecider.oeschneiderschwarz_announced
+    }
+
+    fn with_announced_schneider_schwarz(&self, eschneiderschwarz: ESchneiderSchwarz) -> Option<Box<dyn TRules>> {
+        self.payoutdecider.with_announced_schneider_schwarz(eschneiderschwarz)
+            .map(|payoutdecider| Box::new(Self{payoutdecider, ..self.clone()}) as Box<dyn TRules>)
+    }
+
+    fn stoss_allowed(&self, epi: EPlayerIndex, vecstoss: &[SStoss], _hand: &SHand) -> bool {
+        if let Some(epi_coplayer) = self.oepi_coplayer {
+            (epi==self.epi || epi==epi_coplayer)==(vecstoss.len()%2==1)
+        } else {
+            // coplayer is not known before the game, so only the player offering Hochzeit answers stoss
+            (epi==self.epi)==(vecstoss.len()%2==1)
+        }
+    }
+
+    fn payoutinfos(&self, gamefinishedstiche: SStichSequenceGameFinished, rulestatecache: &SRuleStateCache) -> EnumMap<EPlayerIndex, SPayoutInfo> {
+        fn internal_payoutinfos(
+            rules: &SRulesHochzeit,
+            gamefinishedstiche: SStichSequenceGameFinished,
+            rulestatecache: &SRuleStateCache,
+            playerparties: &impl TPlayerParties,
+        ) -> EnumMap<EPlayerIndex, SPayoutInfo> {
+            let an_payout_no_stock = &rules.payoutdecider.payout(
+                rules,
+                rulestatecache,
+                gamefinishedstiche,
+                playerparties,
+            );
+            assert!(an_payout_no_stock.iter().all(|n_payout_no_stock| 0!=*n_payout_no_stock));
+            let estockaction_playerparty = /*b_player_party_wins*/if 0<an_payout_no_stock[rules.epi] {
+                EStockAction::TakeHalf
+            } else {
+                EStockAction::GiveHalf
+            };
+            EPlayerIndex::map_from_fn(|epi|
+                SPayoutInfo::new(
+                    an_payout_no_stock[epi],
+                    if playerparties.is_primary_party(epi) {estockaction_playerparty} else {EStockAction::Ignore},
+                )
+            )
+        }
+        if let Some(epi_coplayer) = self.coplayer(gamefinishedstiche.get()) {
+            assert_ne!(self.epi, epi_coplayer);
+            internal_payoutinfos(self, gamefinishedstiche, rulestatecache, &SPlayerParties22::new([self.epi, epi_coplayer]))
+        } else {
+            // nobody accepted and the player offering Hochzeit won all stichs, i.e. played alone, so each opponent pays as in a solo
+            // (with a single trumpf this cannot happen, but hands sampled by the AI do not necessarily respect can_be_played)
+            internal_payoutinfos(self, gamefinishedstiche, rulestatecache, &SPlayerParties13::new(self.epi))
+        }
+    }
+
+    fn payouthints(&self, stichseq: &SStichSequence, ahand: &EnumMap<EPlayerIndex, SHand>, rulestatecache: &SRuleStateCache) -> EnumMap<EPlayerIndex, SPayoutHint> {
+        if let Some(epi_coplayer) = self.coplayer(stichseq) {
+            self.payoutdecider.payouthints(self, stichseq, ahand, rulestatecache, &SPlayerParties22::new([self.epi, epi_coplayer]))
+                .map(|tplon_payout| SPayoutHint::new((
+                    // TODO EStockAction
+                    tplon_payout.0.map(|n_payout| SPayoutInfo::new(n_payout, EStockAction::Ignore)),
+                    tplon_payout.1.map(|n_payout| SPayoutInfo::new(n_payout, EStockAction::Ignore)),
+                )))
+        } else {
+            EPlayerIndex::map_from_fn(|_epi| SPayoutHint::new((None, None)))
+        }
+    }
+
+    fn known_parties(&self, stichseq: &SStichSequence) -> SKnownParties {
+        if let Some(epi_coplayer) = self.coplayer(stichseq) {
+            SKnownParties::new_from_playerparties(&SPlayerParties22::new([self.epi, epi_coplayer]))
+        } else if stichseq.game_finished() {
+            SKnownParties::new_from_playerparties(&SPlayerParties13::new(self.epi))
+        } else {
+            SKnownParties::new_primary_players(std::iter::once(self.epi))
+        }
+    }
+
+    fn snapshot_cache_history(&self, stichseq: &SStichSequence) -> usize {
+        self.coplayer(stichseq).map_or(0, |epi_coplayer| epi_coplayer.to_usize()+1)
+    }
+}
@@ -0,0 +1,122 @@
+use crate::primitives::*;
+use crate::rules::{payoutdecider::internal_payout, trumpfdecider::*, *};
+use crate::util::*;
+use itertools::Itertools;
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub enum EKreuzspielParties {
+    EachForHimself, // player with most points wins
+    Alte, // holders of Eichel-Ober and Gras-Ober play together
+}
+
+#[derive(new, Clone, Debug)]
+pub struct SRulesKreuzspiel {
+    n_price : isize,
+    ekreuzspielparties : EKreuzspielParties,
+}
+
+impl fmt::Display for SRulesKreuzspiel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Kreuzspiel")
+    }
+}
+
+pub type STrumpfDeciderKreuzspiel = STrumpfDeciderSchlag<
+    SStaticSchlagOber, STrumpfDeciderSchlag<
+    SStaticSchlagUnter,
+    SStaticFarbeEichel>>;
+
+impl SRulesKreuzspiel {
+    fn payoutinfos_for_parties(&self, playerparties: &impl TPlayerParties, b_primary_party_wins: bool) -> EnumMap<EPlayerIndex, SPayoutInfo> {
+        let an_payout = internal_payout(self.n_price, playerparties, b_primary_party_wins);
+        let estockaction_primary_party = if b_primary_party_wins {
+            EStockAction::TakeHalf
+        } else {
+            EStockAction::GiveHalf
+        };
+        EPlayerIndex::map_from_fn(|epi|
+            SPayoutInfo::new(
+                an_payout[epi],
+                if playerparties.is_primary_party(epi) {estockaction_primary_party} else {EStockAction::Ignore},
+            )
+        )
+    }
+}
+
+impl TRulesNoObj for SRulesKreuzspiel {
+    impl_rules_trumpf_noobj!(STrumpfDeciderKreuzspiel);
+}
+
+impl TRules for SRulesKreuzspiel {
+    impl_rules_trumpf!();
+
+    fn stoss_allowed(&self, _epi: EPlayerIndex, vecstoss: &[SStoss], _hand: &SHand) -> bool {
+        assert!(vecstoss.is_empty());
+        false
+    }
+
+    fn playerindex(&self) -> Option<EPlayerIndex> {
+        None
+    }
+
+    fn announcement_decision(&self, _epi: EPlayerIndex) -> Option<EAnnouncementDecision> {
+        Some(EAnnouncementDecision::Passed) // nobody announced a game
+    }
+
+    fn payoutinfos(&self, _gamefinishedstiche: SStichSequenceGameFinished, rulestatecache: &SRuleStateCache) -> EnumMap<EPlayerIndex, SPayoutInfo> {
+        let points_for_player = |epi| rulestatecache.changing.mapepipointstichcount[epi].n_point;
+        match self.ekreuzspielparties {
+            EKreuzspielParties::EachForHimself => {
+                let n_points_max = unwrap!(EPlayerIndex::values().map(points_for_player).max());
+                if let Ok(epi_winner) = EPlayerIndex::values()
+                    .filter(|epi| n_points_max==points_for_player(*epi))
+                    .exactly_one()
+                {
+                    self.payoutinfos_for_parties(&SPlayerParties13::new(epi_winner), /*b_primary_party_wins*/true)
+                } else {
+                    // several players share the maximum, so nobody wins
+                    EPlayerIndex::map_from_fn(|_epi| SPayoutInfo::new(0, EStockAction::Ignore))
+                }
+            },
+            EKreuzspielParties::Alte => {
+                let epi_eichel_ober = rulestatecache.fixed.who_has_card(SCard::new(EFarbe::Eichel, ESchlag::Ober));
+                let epi_gras_ober = rulestatecache.fixed.who_has_card(SCard::new(EFarbe::Gras, ESchlag::Ober));
+                if epi_eichel_ober==epi_gras_ober {
+                    self.payoutinfos_for_parties(
+                        &SPlayerParties13::new(epi_eichel_ober),
+                        /*b_primary_party_wins*/points_for_player(epi_eichel_ober)>=61,
+                    )
+                } else {
+                    self.payoutinfos_for_parties(
+                        &SPlayerParties22::new([epi_eichel_ober, epi_gras_ober]),
+                        /*b_primary_party_wins*/points_for_player(epi_eichel_ober)+points_for_player(epi_gras_ober)>=61,
+                    )
+                }
+            },
+        }
+    }
+
+    fn known_parties(&self, stichseq: &SStichSequence) -> SKnownParties {
+        match self.ekreuzspielparties {
+            EKreuzspielParties::EachForHimself => SKnownParties::new_each_for_himself(),
+            EKreuzspielParties::Alte => {
+                let vecepi_alte = stichseq.visible_cards()
+                    .filter(|&(_epi, card)| ESchlag::Ober==card.schlag() && matches!(card.farbe(), EFarbe::Eichel|EFarbe::Gras))
+                    .map(|(epi, _card)| epi)
+                    .collect::<Vec<_>>();
+                // both Alte seen: if one player holds both, it is 1 vs 3 as in payoutinfos (SPlayerParties13)
+                if 2==vecepi_alte.len() {
+                    SKnownParties::new_from_fn(|epi| Some(if vecepi_alte.contains(&epi) {0} else {1}))
+                } else {
+                    SKnownParties::new_primary_players(vecepi_alte.into_iter())
+                }
+            },
+        }
+    }
+
+    fn payouthints(&self, _stichseq: &SStichSequence, _ahand: &EnumMap<EPlayerIndex, SHand>, _rulestatecache: &SRuleStateCache) -> EnumMap<EPlayerIndex, SPayoutHint> {
+        // TODO sensible payouthints
+        EPlayerIndex::map_from_fn(|_epi| SPayoutHint::new((None, None)))
+    }
+}
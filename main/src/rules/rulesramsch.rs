@@ -1,3 +1,4 @@
+use crate::ai::rulespecific::airamsch::*;
 use crate::primitives::*;
 use crate::rules::{card_points::*, payoutdecider::internal_payout, trumpfdecider::*, *};
 use crate::util::*;
@@ -10,12 +11,21 @@ pub enum VDurchmarsch {
     AtLeast(isize),
 }
 
-// TODO add Jungfrau (needed for Sauspiel analysis, in particular)
-
 #[derive(new, Clone, Debug)]
 pub struct SRulesRamsch {
     n_price : isize,
     durchmarsch : VDurchmarsch,
+    b_jungfrau : bool, // players without any stich receive double
+    on_price_stock : Option<isize>, // if set, the loser pays the whole stock instead of paying the other players
+    b_schieben : bool, // players may push before the game, each push doubles the payout
+    #[new(default)]
+    n_schieben : usize,
+}
+
+impl SRulesRamsch {
+    pub fn durchmarsch(&self) -> &VDurchmarsch {
+        &self.durchmarsch
+    }
 }
 
 impl fmt::Display for SRulesRamsch {
@@ -46,6 +56,18 @@ impl TRules for SRulesRamsch {
         None
     }
 
+    fn announcement_decision(&self, _epi: EPlayerIndex) -> Option<EAnnouncementDecision> {
+        Some(EAnnouncementDecision::Passed) // nobody announced a game
+    }
+
+    fn known_parties(&self, _stichseq: &SStichSequence) -> SKnownParties {
+        SKnownParties::new_each_for_himself()
+    }
+
+    fn rulespecific_ai<'rules>(&'rules self) -> Option<Box<dyn TRuleSpecificAI + 'rules>> {
+        Some(Box::new(SAIRamsch::new(self)))
+    }
+
     fn payoutinfos(&self, gamefinishedstiche: SStichSequenceGameFinished, rulestatecache: &SRuleStateCache) -> EnumMap<EPlayerIndex, SPayoutInfo> {
         let points_for_player = |epi| rulestatecache.changing.mapepipointstichcount[epi].n_point;
         debug_assert_eq!(
@@ -119,12 +141,46 @@ impl TRules for SRulesRamsch {
             };
             (epi_loser, false)
         };
-        internal_payout(
-            self.n_price,
-            &SPlayerParties13::new(epi_single),
-            b_epi_single_wins,
-        )
-            .map(|n_payout| SPayoutInfo::new(*n_payout, EStockAction::Ignore))
+        let is_jungfrau = |epi| {
+            self.b_jungfrau && 0==rulestatecache.changing.mapepipointstichcount[epi].n_stich
+        };
+        let an_payout = match (b_epi_single_wins, self.on_price_stock) {
+            (false, Some(n_price_stock)) => {
+                // stock payment is doubled for each Jungfrau
+                let n_payout_stock = -n_price_stock * EPlayerIndex::SIZE.as_num::<isize>()
+                    * 2isize.pow(EPlayerIndex::values().filter(|epi| is_jungfrau(*epi)).count().as_num::<u32>());
+                EPlayerIndex::map_from_fn(|epi| if epi==epi_single {n_payout_stock} else {0})
+            },
+            (false, None) => {
+                let an_payout_others = EPlayerIndex::map_from_fn(|epi|
+                    if epi==epi_single {
+                        0
+                    } else if is_jungfrau(epi) {
+                        2 * self.n_price
+                    } else {
+                        self.n_price
+                    }
+                );
+                let n_payout_loser = -an_payout_others.iter().sum::<isize>();
+                EPlayerIndex::map_from_fn(|epi| if epi==epi_single {n_payout_loser} else {an_payout_others[epi]})
+            },
+            (true, _) => internal_payout(
+                self.n_price,
+                &SPlayerParties13::new(epi_single),
+                b_epi_single_wins,
+            ),
+        };
+        an_payout.map(|n_payout| SPayoutInfo::new(
+            *n_payout * 2isize.pow(self.n_schieben.as_num::<u32>()),
+            EStockAction::Ignore,
+        ))
+    }
+
+    fn with_schieben(&self) -> Option<Box<dyn TRules>> {
+        if_then_some!(self.b_schieben, Box::new(SRulesRamsch {
+            n_schieben: self.n_schieben + 1,
+            ..self.clone()
+        }) as Box<dyn TRules>)
     }
 
     fn payouthints(&self, _stichseq: &SStichSequence, _ahand: &EnumMap<EPlayerIndex, SHand>, _rulestatecache: &SRuleStateCache) -> EnumMap<EPlayerIndex, SPayoutHint> {
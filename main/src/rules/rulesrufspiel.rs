@@ -4,16 +4,29 @@ use crate::rules::{payoutdecider::*, trumpfdecider::*, *};
 use crate::util::*;
 use std::{cmp::Ordering, fmt};
 
+#[derive(Clone, Debug, new)]
+pub struct SRufspielParams {
+    pub eschlag_ruf: ESchlag, // Ass (classic) or Zehn (Ruf-Zehn, called by a player holding the corresponding Sau)
+    pub b_davonlaufen: bool, // whether the partner may run away if he has four cards of the called farbe
+}
+
+impl Default for SRufspielParams {
+    fn default() -> Self {
+        SRufspielParams::new(ESchlag::Ass, /*b_davonlaufen*/true)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SRulesRufspiel {
     epi : EPlayerIndex,
     efarbe : EFarbe,
     payoutdecider: SPayoutDeciderPointBased<SPointsToWin61>,
+    rufspielparams: SRufspielParams,
 }
 
 impl fmt::Display for SRulesRufspiel {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Rufspiel mit der {}-Sau", self.efarbe)
+        write!(f, "Rufspiel mit der {}-{}", self.efarbe, if self.is_ruf_zehn() {"Zehn"} else {"Sau"})
     }
 }
 
@@ -24,16 +37,26 @@ pub type STrumpfDeciderRufspiel = STrumpfDeciderSchlag<
 
 impl SRulesRufspiel {
     pub fn new(epi: EPlayerIndex, efarbe: EFarbe, payoutparams: SPayoutDeciderParams) -> SRulesRufspiel {
+        Self::new_with_params(epi, efarbe, payoutparams, SRufspielParams::default())
+    }
+
+    pub fn new_with_params(epi: EPlayerIndex, efarbe: EFarbe, payoutparams: SPayoutDeciderParams, rufspielparams: SRufspielParams) -> SRulesRufspiel {
         assert_ne!(efarbe, EFarbe::Herz);
+        assert!(matches!(rufspielparams.eschlag_ruf, ESchlag::Ass | ESchlag::Zehn));
         SRulesRufspiel {
             epi,
             efarbe,
             payoutdecider: SPayoutDeciderPointBased::new(payoutparams, SPointsToWin61{}),
+            rufspielparams,
         }
     }
 
-    pub fn rufsau(&self) -> SCard {
-        SCard::new(self.efarbe, ESchlag::Ass)
+    pub fn rufkarte(&self) -> SCard {
+        SCard::new(self.efarbe, self.rufspielparams.eschlag_ruf)
+    }
+
+    pub fn is_ruf_zehn(&self) -> bool {
+        ESchlag::Zehn==self.rufspielparams.eschlag_ruf
     }
 
     fn is_ruffarbe(&self, card: SCard) -> bool {
@@ -51,48 +74,53 @@ impl TRulesNoObj for SRulesRufspiel {
     impl_rules_trumpf_noobj!(STrumpfDeciderRufspiel);
 }
 
-struct SPlayerParties22 {
-    aepi_pri: [EPlayerIndex; 2],
-}
-
-impl TPlayerParties for SPlayerParties22 {
-    fn is_primary_party(&self, epi: EPlayerIndex) -> bool {
-        self.aepi_pri[0]==epi || self.aepi_pri[1]==epi
-    }
-    fn multiplier(&self, _epi: EPlayerIndex) -> isize {
-        1
-    }
-}
-
 impl TRules for SRulesRufspiel {
     impl_rules_trumpf!();
 
     fn can_be_played(&self, hand: SFullHand) -> bool {
         let it = || {hand.get().iter().filter(|&card| self.is_ruffarbe(*card))};
-        it().all(|card| card.schlag()!=ESchlag::Ass)
-        && 0<it().count()
+        if self.is_ruf_zehn() {
+            hand.get().contains(&SCard::new(self.efarbe, ESchlag::Ass))
+            && !hand.get().contains(&self.rufkarte())
+        } else {
+            it().all(|card| card.schlag()!=ESchlag::Ass)
+            && 0<it().count()
+        }
     }
 
     fn playerindex(&self) -> Option<EPlayerIndex> {
         Some(self.epi)
     }
 
+    fn called_card(&self) -> Option<SCard> {
+        Some(self.rufkarte())
+    }
+
+    fn announced_schneider_schwarz(&self) -> Option<ESchneiderSchwarz> {
+        self.payoutdecider.oeschneiderschwarz_announced
+    }
+
+    fn with_announced_schneider_schwarz(&self, eschneiderschwarz: ESchneiderSchwarz) -> Option<Box<dyn TRules>> {
+        self.payoutdecider.with_announced_schneider_schwarz(eschneiderschwarz)
+            .map(|payoutdecider| Box::new(Self{payoutdecider, ..self.clone()}) as Box<dyn TRules>)
+    }
+
     fn stoss_allowed(&self, epi: EPlayerIndex, vecstoss: &[SStoss], hand: &SHand) -> bool {
         EKurzLang::from_cards_per_player(hand.cards().len());
-        assert!(epi!=self.epi || !hand.contains(self.rufsau()));
-        (epi==self.epi || hand.contains(self.rufsau())) == (vecstoss.len()%2==1)
+        assert!(epi!=self.epi || !hand.contains(self.rufkarte()));
+        (epi==self.epi || hand.contains(self.rufkarte())) == (vecstoss.len()%2==1)
     }
 
     fn payoutinfos(&self, gamefinishedstiche: SStichSequenceGameFinished, rulestatecache: &SRuleStateCache) -> EnumMap<EPlayerIndex, SPayoutInfo> {
         let epi_coplayer = debug_verify_eq!(
-            rulestatecache.fixed.who_has_card(self.rufsau()),
+            rulestatecache.fixed.who_has_card(self.rufkarte()),
             unwrap!(gamefinishedstiche.get().completed_stichs().iter()
                 .flat_map(|stich| stich.iter())
-                .find(|&(_, card)| *card==self.rufsau())
+                .find(|&(_, card)| *card==self.rufkarte())
                 .map(|(epi, _)| epi))
         );
         assert_ne!(self.epi, epi_coplayer);
-        let playerparties = SPlayerParties22{aepi_pri: [self.epi, epi_coplayer]};
+        let playerparties = SPlayerParties22::new([self.epi, epi_coplayer]);
         let an_payout_no_stock = &self.payoutdecider.payout(
             self,
             rulestatecache,
@@ -122,18 +150,18 @@ impl TRules for SRulesRufspiel {
 
     fn payouthints(&self, stichseq: &SStichSequence, ahand: &EnumMap<EPlayerIndex, SHand>, rulestatecache: &SRuleStateCache) -> EnumMap<EPlayerIndex, SPayoutHint> {
         let epi_coplayer = debug_verify_eq!(
-            rulestatecache.fixed.who_has_card(self.rufsau()),
+            rulestatecache.fixed.who_has_card(self.rufkarte()),
             stichseq.visible_cards()
-                .find(|&(_, card)| *card==self.rufsau())
+                .find(|&(_, card)| *card==self.rufkarte())
                 .map(|(epi, _)| epi)
                 .unwrap_or_else(|| {
                     unwrap!(EPlayerIndex::values().find(|epi|
-                        ahand[*epi].cards().iter().any(|card| *card==self.rufsau())
+                        ahand[*epi].cards().iter().any(|card| *card==self.rufkarte())
                     ))
                 })
         );
         assert_ne!(self.epi, epi_coplayer);
-        self.payoutdecider.payouthints(self, stichseq, ahand, rulestatecache, &SPlayerParties22{aepi_pri: [self.epi, epi_coplayer]})
+        self.payoutdecider.payouthints(self, stichseq, ahand, rulestatecache, &SPlayerParties22::new([self.epi, epi_coplayer]))
             .map(|tplon_payout| SPayoutHint::new((
                 // TODO EStockAction
                 tplon_payout.0.map(|n_payout| SPayoutInfo::new(n_payout, EStockAction::Ignore)),
@@ -141,17 +169,32 @@ impl TRules for SRulesRufspiel {
             )))
     }
 
+    fn snapshot_cache_history(&self, stichseq: &SStichSequence) -> usize {
+        // allowed cards depend on who already played out ruffarbe
+        stichseq.completed_stichs().iter()
+            .filter(|stich| self.is_ruffarbe(*stich.first()))
+            .fold(0, |n_bits, stich| n_bits | (1 << stich.first_playerindex().to_usize()))
+    }
+
+    fn announcement_decision(&self, epi: EPlayerIndex) -> Option<EAnnouncementDecision> {
+        Some(if epi==self.epi {
+            EAnnouncementDecision::AnnouncedRufspiel
+        } else {
+            EAnnouncementDecision::Passed
+        })
+    }
+
     fn all_allowed_cards_first_in_stich(&self, stichseq: &SStichSequence, hand: &SHand) -> SHandVector {
-        if // do we already know who had the rufsau?
+        if // do we already know who had the rufkarte?
             stichseq.completed_stichs().iter()
                 .any(|stich| {
                     assert!(stich.is_full()); // completed_stichs should only process full stichs
                     self.is_ruffarbe(*stich.first()) // gesucht or weggelaufen
-                    || stich.iter().any(|(_, card)| *card==self.rufsau()) // We explicitly traverse all cards because it may be allowed (by exotic rules) to schmier rufsau even if not gesucht.
+                    || stich.iter().any(|(_, card)| *card==self.rufkarte()) // We explicitly traverse all cards because it may be allowed (by exotic rules) to schmier rufkarte even if not gesucht.
                 } )
             // Remark: Player must have 4 cards of ruffarbe on his hand *at this point of time* (i.e. not only at the beginning!)
-            || !hand.contains(self.rufsau())
-            || 4 <= hand.cards().iter()
+            || !hand.contains(self.rufkarte())
+            || self.rufspielparams.b_davonlaufen && 4 <= hand.cards().iter()
                 .filter(|&card| self.is_ruffarbe(*card))
                 .count()
         {
@@ -159,7 +202,7 @@ impl TRules for SRulesRufspiel {
         } else {
             hand.cards().iter()
                 .copied()
-                .filter(|&card| !self.is_ruffarbe(card) || self.rufsau()==card)
+                .filter(|&card| !self.is_ruffarbe(card) || self.rufkarte()==card)
                 .collect()
         }
     }
@@ -171,7 +214,7 @@ impl TRules for SRulesRufspiel {
             assert!(!stichseq.current_stich().is_empty());
             let epi = unwrap!(stichseq.current_stich().current_playerindex());
             let card_first = *stichseq.current_stich().first();
-            if /*either weggelaufen or epi is not partner, and accordingly does not hold rufsau*/stichseq.completed_stichs().iter()
+            if /*either weggelaufen or epi is not partner, and accordingly does not hold rufkarte*/stichseq.completed_stichs().iter()
                 .any(|stich| epi==stich.first_playerindex() && self.is_ruffarbe(*stich.first()))
             {
                 all_allowed_cards_within_stich_distinguish_farbe_frei(
@@ -180,17 +223,17 @@ impl TRules for SRulesRufspiel {
                     hand,
                     /*fn_farbe_not_frei*/|veccard_same_farbe_nonempty| veccard_same_farbe_nonempty,
                 )
-            } else if self.is_ruffarbe(card_first) && hand.contains(self.rufsau()) {
-                std::iter::once(self.rufsau()).collect()
+            } else if self.is_ruffarbe(card_first) && hand.contains(self.rufkarte()) {
+                std::iter::once(self.rufkarte()).collect()
             } else {
                 let veccard_allowed : SHandVector = hand.cards().iter().copied()
                     .filter(|&card| 
-                        self.rufsau()!=card 
+                        self.rufkarte()!=card 
                         && self.trumpforfarbe(card)==self.trumpforfarbe(card_first)
                     )
                     .collect();
                 if veccard_allowed.is_empty() {
-                    hand.cards().iter().copied().filter(|&card| self.rufsau()!=card).collect()
+                    hand.cards().iter().copied().filter(|&card| self.rufkarte()!=card).collect()
                 } else {
                     veccard_allowed
                 }
@@ -198,6 +241,14 @@ impl TRules for SRulesRufspiel {
         }
     }
 
+    fn known_parties(&self, stichseq: &SStichSequence) -> SKnownParties {
+        if let Some((epi_coplayer, _card)) = stichseq.visible_cards().find(|&(_epi, card)| *card==self.rufkarte()) {
+            SKnownParties::new_from_playerparties(&SPlayerParties22::new([self.epi, epi_coplayer]))
+        } else {
+            SKnownParties::new_primary_players(std::iter::once(self.epi))
+        }
+    }
+
     fn rulespecific_ai<'rules>(&'rules self) -> Option<Box<dyn TRuleSpecificAI + 'rules>> {
         Some(Box::new(SAIRufspiel::new(self)))
     }
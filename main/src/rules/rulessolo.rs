@@ -1,3 +1,4 @@
+use crate::ai::rulespecific::aisololike::*;
 use crate::primitives::*;
 use crate::rules::{payoutdecider::*, trumpfdecider::*, *};
 use crate::util::*;
@@ -32,6 +33,12 @@ pub trait TPayoutDeciderSoloLike : Sync + 'static + Clone + fmt::Debug + TPayout
     fn priorityinfo(&self) -> String {
         "".to_string()
     }
+    fn announced_schneider_schwarz(&self) -> Option<ESchneiderSchwarz> {
+        None
+    }
+    fn with_announced_schneider_schwarz(&self, _eschneiderschwarz: ESchneiderSchwarz) -> Option<Self> {
+        None
+    }
 }
 
 impl TPointsToWin for VGameAnnouncementPrioritySoloLike {
@@ -75,6 +82,14 @@ impl TPayoutDeciderSoloLike for SPayoutDeciderPointBased<VGameAnnouncementPriori
         VGameAnnouncementPriority::SoloLike(self.pointstowin.clone())
     }
 
+    fn announced_schneider_schwarz(&self) -> Option<ESchneiderSchwarz> {
+        self.oeschneiderschwarz_announced
+    }
+
+    fn with_announced_schneider_schwarz(&self, eschneiderschwarz: ESchneiderSchwarz) -> Option<Self> {
+        self.with_announced_schneider_schwarz(eschneiderschwarz)
+    }
+
     fn with_increased_prio(&self, prio: &VGameAnnouncementPriority, ebid: EBid) -> Option<Self> {
         use self::VGameAnnouncementPriority::*;
         use self::VGameAnnouncementPrioritySoloLike::*;
@@ -173,39 +188,40 @@ impl TPayoutDeciderSoloLike for SPayoutDeciderTout {
 #[derive(Clone, Debug, new)]
 pub struct SPayoutDeciderSie {
     payoutparams : SPayoutDeciderParams,
-}
-
-fn cards_valid_for_sie_internal<Rules: TRulesNoObj, ItCard: Iterator<Item=SCard>, FnAllowUnter: Fn(EFarbe)->bool>(
-    rules: &Rules,
-    mut itcard: ItCard,
-    fn_allow_unter: FnAllowUnter,
-) -> bool {
-    itcard.all(|card| {
-        let b_card_valid = match card.schlag() {
-            ESchlag::Ober => true,
-            ESchlag::Unter => fn_allow_unter(card.farbe()),
-            ESchlag::S7 | ESchlag::S8 | ESchlag::S9 | ESchlag::Zehn | ESchlag::Koenig | ESchlag::Ass => false,
-        };
-        assert!(!b_card_valid || rules.trumpforfarbe(card).is_trumpf());
-        b_card_valid
-    })
+    esololike: ESoloLike,
 }
 
 // TODO SPayoutDeciderSie should be able to work with any TTrumpfDecider
 fn cards_valid_for_sie<Rules: TRulesNoObj, ItCard: Iterator<Item=SCard>>(
     rules: &Rules,
-    itcard: ItCard,
+    mut itcard: ItCard,
+    esololike: ESoloLike,
     ekurzlang: EKurzLang,
 ) -> bool {
-    match ekurzlang {
-        EKurzLang::Lang => cards_valid_for_sie_internal(rules, itcard, /*fn_allow_unter*/|_| true),
-        EKurzLang::Kurz => cards_valid_for_sie_internal(rules, itcard, /*fn_allow_unter*/|efarbe|
-            match efarbe {
-                EFarbe::Eichel | EFarbe::Gras => true,
-                EFarbe::Herz | EFarbe::Schelln => false,
-            }
-        ),
-    }
+    // Sie requires all cards of eschlag_all, complemented by the highest cards of eschlag_complement
+    let (eschlag_all, eschlag_complement) = match esololike {
+        ESoloLike::Solo => (ESchlag::Ober, ESchlag::Unter),
+        ESoloLike::Wenz => (ESchlag::Unter, ESchlag::Ass),
+        ESoloLike::Geier => (ESchlag::Ober, ESchlag::Ass),
+    };
+    // complement cards fill up the hand: cards_per_player - EFarbe::SIZE of them
+    let slcefarbe_complement : &[EFarbe] = match ekurzlang {
+        EKurzLang::Lang => &[EFarbe::Eichel, EFarbe::Gras, EFarbe::Herz, EFarbe::Schelln],
+        #[cfg(not(feature="dreier"))]
+        EKurzLang::Kurz => &[EFarbe::Eichel, EFarbe::Gras],
+        #[cfg(feature="dreier")]
+        EKurzLang::Kurz => &[EFarbe::Eichel, EFarbe::Gras, EFarbe::Herz, EFarbe::Schelln],
+    };
+    itcard.all(|card| {
+        if card.schlag()==eschlag_all {
+            assert!(rules.trumpforfarbe(card).is_trumpf());
+            true
+        } else if card.schlag()==eschlag_complement {
+            slcefarbe_complement.contains(&card.farbe())
+        } else {
+            false
+        }
+    })
 }
 
 impl TPayoutDecider for SPayoutDeciderSie {
@@ -228,6 +244,7 @@ impl TPayoutDecider for SPayoutDeciderSie {
             /*b_primary_party_wins*/cards_valid_for_sie(
                 rules,
                 gamefinishedstiche.get().completed_stichs().iter().map(|stich| stich[playerparties13.primary_player()]),
+                self.esololike,
                 gamefinishedstiche.get().kurzlang(),
             )
         )
@@ -249,6 +266,7 @@ impl TPayoutDecider for SPayoutDeciderSie {
             !cards_valid_for_sie(
                 rules,
                 itcard.clone(),
+                self.esololike,
                 stichseq.kurzlang(),
             )
         {
@@ -303,6 +321,23 @@ impl<StaticEPI: TStaticValue<EPlayerIndex>, TrumpfDecider: TTrumpfDecider, Payou
 impl<StaticEPI: TStaticValue<EPlayerIndex>, TrumpfDecider: TTrumpfDecider, PayoutDecider: TPayoutDeciderSoloLike> TRules for SRulesSoloLike<StaticEPI, TrumpfDecider, PayoutDecider> {
     impl_rules_trumpf!();
     impl_single_play!();
+
+    fn announcement_decision(&self, epi: EPlayerIndex) -> Option<EAnnouncementDecision> {
+        if_then_some!(epi==self.internal_playerindex(), EAnnouncementDecision::AnnouncedSolo)
+    }
+
+    fn announced_schneider_schwarz(&self) -> Option<ESchneiderSchwarz> {
+        self.payoutdecider.announced_schneider_schwarz()
+    }
+
+    fn with_announced_schneider_schwarz(&self, eschneiderschwarz: ESchneiderSchwarz) -> Option<Box<dyn TRules>> {
+        self.payoutdecider.with_announced_schneider_schwarz(eschneiderschwarz)
+            .map(|payoutdecider| Box::new(Self::new(payoutdecider, self.str_name.clone())) as Box<dyn TRules>)
+    }
+
+    fn rulespecific_ai<'rules>(&'rules self) -> Option<Box<dyn TRuleSpecificAI + 'rules>> {
+        Some(Box::new(SAISoloLike::new(self, self.internal_playerindex())))
+    }
 }
 
 impl<StaticEPI: TStaticValue<EPlayerIndex>, TrumpfDecider: TTrumpfDecider, PayoutDecider: TPayoutDeciderSoloLike> SRulesSoloLike<StaticEPI, TrumpfDecider, PayoutDecider> {
@@ -365,6 +400,7 @@ pub fn sololike(
             EPlayerIndex::EPI0 => SStaticEPI0,
             EPlayerIndex::EPI1 => SStaticEPI1,
             EPlayerIndex::EPI2 => SStaticEPI2,
+            #[cfg(not(feature="dreier"))]
             EPlayerIndex::EPI3 => SStaticEPI3,
         },
         match (oefarbe.into()) {
@@ -13,6 +13,10 @@ macro_rules! impl_single_play {() => {
         (epi==self.internal_playerindex())==(vecstoss.len()%2==1)
     }
 
+    fn known_parties(&self, _stichseq: &SStichSequence) -> SKnownParties {
+        SKnownParties::new_from_playerparties(&SPlayerParties13::new(self.internal_playerindex()))
+    }
+
     fn payoutinfos(&self, gamefinishedstiche: SStichSequenceGameFinished, rulestatecache: &SRuleStateCache) -> EnumMap<EPlayerIndex, SPayoutInfo> {
         self.payoutdecider.payout(
             self,
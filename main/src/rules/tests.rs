@@ -1,10 +1,12 @@
 use crate::game::*;
-use crate::rules::ruleset::SStossParams;
+use crate::rules::ruleset::{EStossTime, SStossParams};
 use crate::game_analysis::*;
 use crate::primitives::{card::card_values::*, *};
 use crate::rules::{
     payoutdecider::*, rulesbettel::*, rulesramsch::*, rulesrufspiel::*, rulessolo::*, *,
 };
+#[cfg(not(feature="dreier"))]
+use crate::rules::{ruleshochzeit::*, ruleskreuzspiel::*};
 use crate::util::*;
 
 fn internal_test_rules(
@@ -15,7 +17,7 @@ fn internal_test_rules(
     vecn_stoss: Vec<usize>,
     n_stock: isize,
     slcstich_test: &[SStich],
-    (an_payout, n_stock_payout): ([isize; 4], isize),
+    (an_payout, n_stock_payout): ([isize; EPlayerIndex::SIZE], isize),
 ) {
     println!("Testing rules: {}", str_info);
     // TODO? check _ahand
@@ -27,7 +29,7 @@ fn internal_test_rules(
                 vecn_doubling.contains(&epi.to_usize())
             ).into_raw(),
         ),
-        /*ostossparams*/Some(SStossParams::new(/*n_stoss_max*/4)),
+        /*ostossparams*/Some(SStossParams::new(/*n_stoss_max*/4, EStossTime::BeforeFirstCard, /*b_announce_schneider_schwarz*/false)),
         rules.box_clone(),
         n_stock,
     );
@@ -61,11 +63,11 @@ impl TCardArrayKurzLang for [SCard; 8] {
 pub fn test_rules<CardArrayKurzLang: TCardArrayKurzLang>(
     str_info: &str,
     rules: &dyn TRules,
-    aacard_hand: [CardArrayKurzLang; 4],
+    aacard_hand: [CardArrayKurzLang; EPlayerIndex::SIZE],
     vecn_doubling: Vec<usize>,
     vecn_stoss: Vec<usize>,
-    vectplepiacard_stich: &[(EPlayerIndex, [SCard; 4])],
-    an_payout: [isize; 4],
+    vectplepiacard_stich: &[(EPlayerIndex, [SCard; EPlayerIndex::SIZE])],
+    an_payout: [isize; EPlayerIndex::SIZE],
 ) {
     internal_test_rules(
         str_info,
@@ -86,8 +88,8 @@ pub fn test_rules_manual(
     vecn_doubling: Vec<usize>,
     vecn_stoss: Vec<usize>,
     n_stock: isize,
-    vectplepiacard_stich: &[(EPlayerIndex, [SCard; 4])],
-    (an_payout, n_stock_payout): ([isize; 4], isize),
+    vectplepiacard_stich: &[(EPlayerIndex, [SCard; EPlayerIndex::SIZE])],
+    (an_payout, n_stock_payout): ([isize; EPlayerIndex::SIZE], isize),
 ) {
     let vecstich = make_stich_vector(vectplepiacard_stich);
     internal_test_rules(
@@ -138,6 +140,7 @@ impl TPayoutDeciderSoloLikeDefault for SPayoutDeciderTout {
 }
 
 #[test]
+#[cfg(not(feature="dreier"))]
 fn test_rulesrufspiel_weglaufen() {
     use EPlayerIndex::*;
     test_rules(
@@ -152,6 +155,59 @@ fn test_rulesrufspiel_weglaufen() {
 }
 
 #[test]
+#[cfg(not(feature="dreier"))]
+fn test_rulesrufspiel_variants() {
+    use EPlayerIndex::*;
+    let rufspiel = |epi, efarbe, eschlag_ruf, b_davonlaufen| SRulesRufspiel::new_with_params(
+        epi,
+        efarbe,
+        SPayoutDeciderParams::new(/*n_payout_base*/20, /*n_payout_schneider_schwarz*/10, SLaufendeParams::new(10, 3)),
+        SRufspielParams::new(eschlag_ruf, b_davonlaufen),
+    );
+    { // Davonlaufen
+        let stichseq = SStichSequence::new_from_cards(
+            EKurzLang::Lang,
+            [H8,H7,HK,HZ].iter().copied(),
+            &rufspiel(EPI0, EFarbe::Gras, ESchlag::Ass, /*b_davonlaufen*/true),
+        );
+        let hand = SHand::new_from_iter([EK,GA,GZ,G9,G7,SZ,S8]);
+        assert_eq!(
+            rufspiel(EPI0, EFarbe::Gras, ESchlag::Ass, /*b_davonlaufen*/true).all_allowed_cards_first_in_stich(&stichseq, &hand),
+            hand.cards().clone(),
+        );
+        assert_eq!(
+            rufspiel(EPI0, EFarbe::Gras, ESchlag::Ass, /*b_davonlaufen*/false).all_allowed_cards_first_in_stich(&stichseq, &hand),
+            [EK,GA,SZ,S8].iter().copied().collect::<SHandVector>(),
+        );
+    }
+    { // Ruf-Zehn
+        let acard_without_sau = [HO,GU,SU,HA,H9,H8,GK,G8];
+        let acard_with_sau = [HO,GU,SU,HA,H9,GA,GK,G8];
+        for (acard, eschlag_ruf, b_can_be_played) in [
+            (acard_without_sau, ESchlag::Ass, true),
+            (acard_without_sau, ESchlag::Zehn, false),
+            (acard_with_sau, ESchlag::Ass, false),
+            (acard_with_sau, ESchlag::Zehn, true),
+        ] {
+            assert_eq!(
+                rufspiel(EPI0, EFarbe::Gras, eschlag_ruf, /*b_davonlaufen*/true).can_be_played(SFullHand::new(&acard, EKurzLang::Lang)),
+                b_can_be_played,
+            );
+        }
+        test_rules(
+            "0 calls Eichel-Zehn",
+            &rufspiel(EPI0, EFarbe::Eichel, ESchlag::Zehn, /*b_davonlaufen*/true),
+            [[EO,GO,HO,SO,EA,GA,SA,HA],[EU,GU,HU,SU,EZ,E9,G9,S9],[HZ,HK,H9,H8,EK,G8,GZ,S8],[H7,E8,E7,GK,G7,SZ,SK,S7],],
+            vec![],
+            vec![],
+            &[(EPI0, [EA,EZ,EK,E8]),(EPI0, [EO,EU,HZ,H7]),(EPI0, [GO,GU,HK,GK]),(EPI0, [HO,HU,H9,G7]),(EPI0, [SO,SU,H8,E7]),(EPI0, [HA,E9,G8,S7]),(EPI0, [GA,G9,GZ,SZ]),(EPI0, [SA,S9,S8,SK]),],
+            [130, 130, -130, -130],
+        );
+    }
+}
+
+#[test]
+#[cfg(not(feature="dreier"))]
 fn test_rulesrufspiel() {
     use EPlayerIndex::*;
     test_rules(
@@ -427,6 +483,7 @@ fn test_rulesrufspiel() {
 }
 
 #[test]
+#[cfg(not(feature="dreier"))]
 fn test_rulesfarbwenz() {
     use EPlayerIndex::*;
     test_rules(
@@ -585,6 +642,7 @@ fn test_rulesfarbwenz() {
 }
 
 #[test]
+#[cfg(not(feature="dreier"))]
 fn test_ruleswenz() {
     use EPlayerIndex::*;
     test_rules(
@@ -743,6 +801,7 @@ fn test_ruleswenz() {
 }
 
 #[test]
+#[cfg(not(feature="dreier"))]
 fn test_rulessolo() {
     use EPlayerIndex::*;
     test_rules(
@@ -1707,6 +1766,7 @@ fn test_rulessolo() {
 }
 
 #[test]
+#[cfg(not(feature="dreier"))]
 fn test_rulesgeier() {
     use EPlayerIndex::*;
     test_rules(
@@ -1820,11 +1880,12 @@ fn test_rulesgeier() {
 }
 
 #[test]
+#[cfg(not(feature="dreier"))]
 fn test_rulesramsch() {
     use EPlayerIndex::*;
     test_rules_manual(
         "0 has durchmarsch all",
-        &SRulesRamsch::new(10, VDurchmarsch::All),
+        &SRulesRamsch::new(10, VDurchmarsch::All, /*b_jungfrau*/false, /*on_price_stock*/None, /*b_schieben*/false),
         vec![],
         vec![],
         /*n_stock*/20,
@@ -1842,7 +1903,7 @@ fn test_rulesramsch() {
     );
     test_rules_manual(
         "0 has durchmarsch 120",
-        &SRulesRamsch::new(10, VDurchmarsch::AtLeast(120)),
+        &SRulesRamsch::new(10, VDurchmarsch::AtLeast(120), /*b_jungfrau*/false, /*on_price_stock*/None, /*b_schieben*/false),
         vec![],
         vec![],
         /*n_stock*/160,
@@ -1860,7 +1921,7 @@ fn test_rulesramsch() {
     );
     test_rules_manual(
         "0 has 120, but no durchmarsch",
-        &SRulesRamsch::new(10, VDurchmarsch::All),
+        &SRulesRamsch::new(10, VDurchmarsch::All, /*b_jungfrau*/false, /*on_price_stock*/None, /*b_schieben*/false),
         vec![],
         vec![],
         /*n_stock*/40,
@@ -1876,14 +1937,195 @@ fn test_rulesramsch() {
         ],
         ([-30, 10, 10, 10], 0),
     );
+    test_rules_manual(
+        "0 has 120 and pays into stock",
+        &SRulesRamsch::new(10, VDurchmarsch::All, /*b_jungfrau*/false, /*on_price_stock*/Some(5), /*b_schieben*/false),
+        vec![],
+        vec![],
+        /*n_stock*/40,
+        &[
+            (EPI0, [EO,GO,HO,SO]),
+            (EPI0, [EU,GU,HU,SU]),
+            (EPI0, [HA,HZ,HK,H9]),
+            (EPI0, [EA,EZ,EK,E9]),
+            (EPI0, [GA,GZ,GK,G9]),
+            (EPI0, [SA,SZ,SK,S9]),
+            (EPI0, [E8,E7,G8,G7]),
+            (EPI0, [H7,H8,S8,S7]),
+        ],
+        ([-20, 0, 0, 0], 20),
+    );
+    test_rules_manual(
+        "0 has durchmarsch, stock untouched",
+        &SRulesRamsch::new(10, VDurchmarsch::All, /*b_jungfrau*/false, /*on_price_stock*/Some(5), /*b_schieben*/false),
+        vec![],
+        vec![],
+        /*n_stock*/40,
+        &[
+            (EPI0, [EO,GO,HO,SO]),
+            (EPI0, [EU,GU,HU,SU]),
+            (EPI0, [HA,HZ,HK,H9]),
+            (EPI0, [EA,EZ,EK,E9]),
+            (EPI0, [GA,GZ,GK,G9]),
+            (EPI0, [SA,SZ,SK,S9]),
+            (EPI0, [E8,E7,G8,G7]),
+            (EPI0, [H8,H7,S8,S7]),
+        ],
+        ([30, -10, -10, -10], 0),
+    );
+    let astich_jungfrau = [
+        (EPI0, [EO,GO,HO,SO]),
+        (EPI0, [EU,GU,HU,SU]),
+        (EPI0, [HA,HZ,HK,H9]),
+        (EPI0, [EA,EZ,EK,E9]),
+        (EPI0, [GA,GZ,GK,G9]),
+        (EPI0, [SA,SZ,SK,S9]),
+        (EPI0, [E8,E7,G8,G7]),
+        (EPI0, [H7,H8,S8,S7]),
+    ];
+    test_rules_manual(
+        "2 and 3 are Jungfrau",
+        &SRulesRamsch::new(10, VDurchmarsch::All, /*b_jungfrau*/true, /*on_price_stock*/None, /*b_schieben*/false),
+        vec![],
+        vec![],
+        /*n_stock*/0,
+        &astich_jungfrau,
+        ([-50, 10, 20, 20], 0),
+    );
+    test_rules_manual(
+        "2 and 3 are Jungfrau, 0 pays into stock",
+        &SRulesRamsch::new(10, VDurchmarsch::All, /*b_jungfrau*/true, /*on_price_stock*/Some(5), /*b_schieben*/false),
+        vec![],
+        vec![],
+        /*n_stock*/0,
+        &astich_jungfrau,
+        ([-80, 0, 0, 0], 80),
+    );
+    test_rules_manual(
+        "2 and 3 are Jungfrau, two players pushed",
+        unwrap!(unwrap!(
+            SRulesRamsch::new(10, VDurchmarsch::All, /*b_jungfrau*/true, /*on_price_stock*/None, /*b_schieben*/true)
+                .with_schieben()
+        ).with_schieben()).as_ref(),
+        vec![],
+        vec![],
+        /*n_stock*/0,
+        &astich_jungfrau,
+        ([-200, 40, 80, 80], 0),
+    );
+    assert!(SRulesRamsch::new(10, VDurchmarsch::All, /*b_jungfrau*/false, /*on_price_stock*/None, /*b_schieben*/false).with_schieben().is_none());
+}
+
+#[test]
+#[cfg(not(feature="dreier"))]
+fn test_ruleskreuzspiel() {
+    use EPlayerIndex::*;
+    test_rules_manual(
+        "0 has most points",
+        &SRulesKreuzspiel::new(10, EKreuzspielParties::EachForHimself),
+        vec![],
+        vec![],
+        /*n_stock*/20,
+        &[
+            (EPI0, [EO,EA,E8,SA]),
+            (EPI0, [GO,EZ,E7,SZ]),
+            (EPI0, [HO,EK,HA,SK]),
+            (EPI0, [SO,E9,HZ,S9]),
+            (EPI0, [EU,GA,HK,S8]),
+            (EPI0, [GU,GZ,H9,S7]),
+            (EPI0, [HU,GK,G8,H8]),
+            (EPI0, [SU,G9,G7,H7]),
+        ],
+        ([40, -10, -10, -10], -10),
+    );
+    test_rules_manual(
+        "Alte 0 and 1 win",
+        &SRulesKreuzspiel::new(10, EKreuzspielParties::Alte),
+        vec![],
+        vec![],
+        /*n_stock*/40,
+        &[
+            (EPI0, [EO,GO,E8,SA]),
+            (EPI0, [HO,EZ,E7,SZ]),
+            (EPI0, [SO,EK,HA,SK]),
+            (EPI0, [EU,E9,HZ,S9]),
+            (EPI0, [GU,GA,HK,S8]),
+            (EPI0, [HU,GZ,H9,S7]),
+            (EPI0, [SU,GK,G8,H8]),
+            (EPI0, [EA,G9,G7,H7]),
+        ],
+        ([30, 30, -10, -10], -40),
+    );
+    test_rules_manual(
+        "Alte 0 and 1 lose",
+        &SRulesKreuzspiel::new(10, EKreuzspielParties::Alte),
+        vec![],
+        vec![],
+        /*n_stock*/20,
+        &[
+            (EPI0, [GA,SA,EA,HA]),
+            (EPI2, [HO,EK,EO,GO]),
+            (EPI0, [GZ,SZ,EZ,HZ]),
+            (EPI2, [SO,E9,GK,SK]),
+            (EPI2, [EU,E8,G9,S9]),
+            (EPI2, [GU,E7,G8,S8]),
+            (EPI2, [HU,HK,G7,S7]),
+            (EPI2, [SU,H9,H7,H8]),
+        ],
+        ([-20, -20, 10, 10], 20),
+    );
+}
+
+#[test]
+#[cfg(not(feature="dreier"))]
+fn test_rulessie() {
+    use EPlayerIndex::*;
+    let payoutparams = SPayoutDeciderParams::new(/*n_payout_base*/50, /*n_payout_schneider_schwarz*/10, SLaufendeParams::new(10, 2));
+    test_rules_manual(
+        "1 wins Wenz-Sie",
+        sololike(EPI1, None, ESoloLike::Wenz, SPayoutDeciderSie::new(payoutparams.clone(), ESoloLike::Wenz)).upcast(),
+        vec![],
+        vec![],
+        /*n_stock*/0,
+        &[
+            (EPI0, [EO,EA,EK,E8]),
+            (EPI1, [EU,E9,E7,EZ]),
+            (EPI1, [GU,G9,G7,GZ]),
+            (EPI1, [HU,H9,H7,HZ]),
+            (EPI1, [SU,S9,S7,SZ]),
+            (EPI1, [GA,GK,G8,GO]),
+            (EPI1, [HA,HK,H8,HO]),
+            (EPI1, [SA,SK,S8,SO]),
+        ],
+        ([-520, 1560, -520, -520], 0),
+    );
+    test_rules_manual(
+        "1 loses Geier-Sie",
+        sololike(EPI1, None, ESoloLike::Geier, SPayoutDeciderSie::new(payoutparams, ESoloLike::Geier)).upcast(),
+        vec![],
+        vec![],
+        /*n_stock*/0,
+        &[
+            (EPI0, [EZ,EA,EK,E8]),
+            (EPI1, [EO,E9,E7,EU]),
+            (EPI1, [GO,G9,G7,GU]),
+            (EPI1, [HO,H9,H7,HU]),
+            (EPI1, [SO,S9,S7,SU]),
+            (EPI1, [GA,GK,G8,GZ]),
+            (EPI1, [HA,HK,H8,HZ]),
+            (EPI1, [SK,SA,S8,SZ]),
+        ],
+        ([520, -1560, 520, 520], 0),
+    );
 }
 
 #[test]
+#[cfg(not(feature="dreier"))]
 fn test_rulesbettel() {
     use EPlayerIndex::*;
     test_rules_manual(
         "3 wins Bettel",
-        &SRulesBettel::<SBettelAllAllowedCardsWithinStichNormal>::new(EPlayerIndex::EPI3, /*i_prio*/0, /*n_payout_base*/10),
+        &SRulesBettel::<SBettelAllAllowedCardsWithinStichNormal>::new(EPlayerIndex::EPI3, /*i_prio*/0, /*n_payout_base*/10, /*b_brett*/false),
         vec![],
         vec![],
         /*n_stock*/20,
@@ -1901,7 +2143,7 @@ fn test_rulesbettel() {
     );
     test_rules_manual(
         "2 looses Bettel",
-        &SRulesBettel::<SBettelAllAllowedCardsWithinStichNormal>::new(EPlayerIndex::EPI2, /*i_prio*/0, /*n_payout_base*/10),
+        &SRulesBettel::<SBettelAllAllowedCardsWithinStichNormal>::new(EPlayerIndex::EPI2, /*i_prio*/0, /*n_payout_base*/10, /*b_brett*/false),
         vec![],
         vec![],
         /*n_stock*/40,
@@ -1917,9 +2159,234 @@ fn test_rulesbettel() {
         ],
         ([10, 10, -30, 10], 0),
     );
+    test_rules_manual(
+        "3 wins Bettel with Ober and Unter as trumpf",
+        &SRulesBettel::<SBettelAllAllowedCardsWithinStichNormal, STrumpfDeciderBettelOberUnter>::new(EPlayerIndex::EPI3, /*i_prio*/0, /*n_payout_base*/10, /*b_brett*/false),
+        vec![],
+        vec![],
+        /*n_stock*/0,
+        &[
+            (EPI0, [EO,EU,E9,SU]),
+            (EPI0, [EA,EK,EZ,E7]),
+            (EPI0, [GA,GK,GZ,G7]),
+            (EPI0, [HA,HK,HZ,H7]),
+            (EPI0, [SA,SK,SZ,S7]),
+            (EPI0, [GO,GU,G9,G8]),
+            (EPI0, [HO,HU,H9,H8]),
+            (EPI0, [SO,S8,S9,E8]),
+        ],
+        ([-10, -10, -10, 30], 0),
+    );
+    {
+        let rulesbettel = SRulesBettel::<SBettelAllAllowedCardsWithinStichNormal>::new(EPlayerIndex::EPI3, /*i_prio*/0, /*n_payout_base*/10, /*b_brett*/true);
+        let mut stichseq = SStichSequence::new(EKurzLang::Lang);
+        for card in [EO,EZ,EK,E9] {
+            assert_eq!(rulesbettel.playerindex_open_hand(&stichseq), None);
+            stichseq.zugeben(card, &rulesbettel);
+        }
+        assert_eq!(rulesbettel.playerindex_open_hand(&stichseq), Some(EPlayerIndex::EPI3));
+    }
+}
+
+#[test]
+#[cfg(not(feature="dreier"))]
+fn test_ruleshochzeit() {
+    use EPlayerIndex::*;
+    let ruleshochzeit_new_test = |epi| SRulesHochzeit::new(
+        epi,
+        SPayoutDeciderParams::new(
+            /*n_payout_base*/20,
+            /*n_payout_schneider_schwarz*/10,
+            SLaufendeParams::new(10, 3),
+        ),
+    );
+    test_rules_manual(
+        "0 gets coplayer 1 in first stich, 2 gives stoss",
+        &ruleshochzeit_new_test(EPI0),
+        vec![],
+        vec![2],
+        /*n_stock*/0,
+        &[
+            (EPI0, [EA, SU, E7, S7]),
+            (EPI1, [EO, H8, G8, H7]),
+            (EPI1, [GO, H9, G7, EZ]),
+            (EPI1, [HO, HK, SA, EK]),
+            (EPI1, [SO, HZ, SZ, E9]),
+            (EPI1, [EU, HA, SK, GA]),
+            (EPI1, [GU, E8, S9, GZ]),
+            (EPI1, [HU, G9, S8, GK]),
+        ],
+        ([240, 240, -240, -240], 0),
+    );
+    test_rules_manual(
+        "1 wins first stich himself, gets coplayer 2 in second stich",
+        &ruleshochzeit_new_test(EPI1),
+        vec![],
+        vec![],
+        /*n_stock*/20,
+        &[
+            (EPI0, [E7, EA, G7, EK]),
+            (EPI1, [EZ, EO, HA, E9]),
+            (EPI2, [SA, HZ, S7, G8]),
+            (EPI3, [EU, H7, SU, GO]),
+            (EPI2, [SZ, HK, S8, G9]),
+            (EPI3, [GU, H8, GK, HO]),
+            (EPI2, [SK, H9, S9, GA]),
+            (EPI3, [HU, E8, GZ, SO]),
+        ],
+        ([-60, 70, 70, -60], -20),
+    );
+    test_rules_manual(
+        "2 accepts offer of 0, 1 wins first stich",
+        unwrap!(ruleshochzeit_new_test(EPI0).with_offer_accepted(EPI2)).as_ref(),
+        vec![],
+        vec![],
+        /*n_stock*/0,
+        &[
+            (EPI0, [EA, SU, E7, S7]),
+            (EPI1, [EO, H8, G8, H7]),
+            (EPI1, [GO, H9, G7, EZ]),
+            (EPI1, [HO, HK, SA, EK]),
+            (EPI1, [SO, HZ, SZ, E9]),
+            (EPI1, [EU, HA, SK, GA]),
+            (EPI1, [GU, E8, S9, GZ]),
+            (EPI1, [HU, G9, S8, GK]),
+        ],
+        ([-120, 120, -120, 120], 0),
+    );
+    test_rules_manual(
+        "0 wins all stichs, so nobody becomes coplayer", // only possible for hands violating can_be_played, e.g. sampled by the AI
+        &ruleshochzeit_new_test(EPI0),
+        vec![],
+        vec![],
+        /*n_stock*/20,
+        &[
+            (EPI0, [EO, HA, HZ, HK]),
+            (EPI0, [GO, H9, H8, H7]),
+            (EPI0, [HO, EA, GA, SA]),
+            (EPI0, [SO, EZ, GZ, SZ]),
+            (EPI0, [EU, EK, GK, SK]),
+            (EPI0, [GU, E9, G9, S9]),
+            (EPI0, [HU, E8, G8, S8]),
+            (EPI0, [SU, E7, G7, S7]),
+        ],
+        ([370, -120, -120, -120], -10),
+    );
 }
 
 #[test]
+#[cfg(not(feature="dreier"))]
+fn test_hochzeit_offer() {
+    use EPlayerIndex::*;
+    let ruleset = unwrap!(crate::rules::ruleset::SRuleSet::from_string(
+        r"
+        base-price=10
+        solo-price=50
+        lauf-min=3
+        [hochzeit]
+        [stock]
+        ",
+    ));
+    let rules = unwrap!(crate::rules::ruleset::allowed_rules(
+        &ruleset.avecrulegroup[EPI0],
+        SFullHand::new(&[EO, EA, EZ, EK, E9, GA, GZ, GK], EKurzLang::Lang),
+    ).find_map(|orules| orules.filter(|rules| rules.to_string()=="Hochzeit")));
+    let determinerules = SDetermineRules::new(
+        EPlayerIndex::map_from_raw([
+            [EO, EA, EZ, EK, E9, GA, GZ, GK],
+            [GO, HO, SO, EU, GU, HU, SU, HA],
+            [HZ, HK, H9, H8, H7, E8, E7, G9],
+            [G8, G7, SA, SZ, SK, S9, S8, S7],
+        ]).map(|acard| acard.to_hand_vector()),
+        SDoublings::new_full(SStaticEPI0{}, [false; EPlayerIndex::SIZE]),
+        ruleset.clone(),
+        /*vectplepirules_queued*/vec![],
+        /*n_stock*/0,
+        (EPI0, TActivelyPlayableRulesBoxClone::box_clone(rules)),
+    );
+    let mut hochzeit = match unwrap!(determinerules.finish()) {
+        VHochzeitOrGame::Hochzeit(hochzeit) => hochzeit,
+        VHochzeitOrGame::Game(_game) => panic!("Hochzeit requires an offer"),
+    };
+    assert_eq!(hochzeit.which_player_can_do_something(), Some(EPI1));
+    assert!(hochzeit.cards_to_exchange(EPI1).next().is_none()); // only trumpfs
+    unwrap!(hochzeit.accept(EPI1, None));
+    assert_eq!(hochzeit.which_player_can_do_something(), Some(EPI2));
+    assert!(hochzeit.accept(EPI2, Some(HZ)).is_err()); // trumpf cannot be given back
+    assert!(hochzeit.accept(EPI2, Some(SA)).is_err()); // not in hand
+    unwrap!(hochzeit.accept(EPI2, Some(E8)));
+    assert_eq!(hochzeit.which_player_can_do_something(), None);
+    let game = unwrap!(hochzeit.finish());
+    assert_eq!(game.rules.to_string(), "Hochzeit mit 2");
+    assert!(game.ahand[EPI0].contains(E8) && !game.ahand[EPI0].contains(EO));
+    assert!(game.ahand[EPI2].contains(EO) && !game.ahand[EPI2].contains(E8));
+    assert!(game.rules.can_be_played(SFullHand::new(game.ahand[EPI0].cards(), EKurzLang::Lang)));
+}
+
+#[test]
+#[cfg(not(feature="dreier"))]
+fn test_announced_schneider_schwarz() {
+    use EPlayerIndex::*;
+    let rulesrufspiel = rulesrufspiel_new_test(EPlayerIndex::EPI0, EFarbe::Eichel, 20, 10, SLaufendeParams::new(10, 3));
+    for (oeschneiderschwarz, an_payout) in [
+        (None, [20, 20, -20, -20]),
+        (Some(ESchneiderSchwarz::Schneider), [-40, -40, 40, 40]), // missed announcement loses the game
+        (Some(ESchneiderSchwarz::Schwarz), [-60, -60, 60, 60]),
+    ] {
+        test_rules(
+            "../../testdata/games/10.html",
+            oeschneiderschwarz.map_or_else(
+                || TRulesBoxClone::box_clone(&rulesrufspiel),
+                |eschneiderschwarz| unwrap!(rulesrufspiel.with_announced_schneider_schwarz(eschneiderschwarz)),
+            ).as_ref(),
+            [[HO,SO,GU,SU,EK,GA,S9,S7],[GO,HK,H8,H7,EA,SA,SK,S8],[EU,HU,HA,EZ,E7,GZ,G9,G8],[EO,HZ,H9,E9,E8,GK,G7,SZ],],
+            vec![],
+            vec![],
+            &[(EPI0, [SU,GO,HU,H9]),(EPI1, [H8,HA,EO,GU]),(EPI3, [E8,EK,EA,E7]),(EPI1, [SA,EU,SZ,S7]),(EPI2, [G9,G7,GA,SK]),(EPI0, [HO,H7,G8,HZ]),(EPI0, [SO,HK,EZ,E9]),(EPI0, [S9,S8,GZ,GK]),],
+            an_payout,
+        );
+    }
+    let rulessolo = sololike(EPI0, EFarbe::Herz, ESoloLike::Solo, SPayoutDeciderPointBased::default_payoutdecider(/*n_payout_base*/50, /*n_payout_schneider_schwarz*/10, SLaufendeParams::new(10, 3)));
+    let vectplepiacard_stich = [(EPI0, [EO,HA,EK,G8]),(EPI0, [GO,HZ,E9,G7]),(EPI0, [HO,HK,E8,SA]),(EPI0, [SO,H9,E7,SZ]),(EPI0, [EU,H8,GA,SK]),(EPI0, [GU,H7,GZ,S9]),(EPI0, [HU,EA,GK,S8]),(EPI0, [SU,EZ,G9,S7]),];
+    for (oeschneiderschwarz, an_payout) in [
+        (None, [450, -150, -150, -150]),
+        (Some(ESchneiderSchwarz::Schneider), [480, -160, -160, -160]),
+        (Some(ESchneiderSchwarz::Schwarz), [510, -170, -170, -170]),
+    ] {
+        test_rules_manual(
+            "Herz-Solo schwarz",
+            oeschneiderschwarz.map_or_else(
+                || TRulesBoxClone::box_clone(rulessolo.upcast()),
+                |eschneiderschwarz| unwrap!(rulessolo.upcast().with_announced_schneider_schwarz(eschneiderschwarz)),
+            ).as_ref(),
+            vec![],
+            vec![],
+            /*n_stock*/0,
+            &vectplepiacard_stich,
+            (an_payout, 0),
+        );
+    }
+    let vecstich = make_stich_vector(&vectplepiacard_stich);
+    let mut game = SGame::new(
+        EPlayerIndex::map_from_fn(|epi| vecstich.iter().map(|stich| stich[epi]).collect()),
+        SDoublings::new_full(SStaticEPI0{}, [false; EPlayerIndex::SIZE]),
+        /*ostossparams*/Some(SStossParams::new(/*n_stoss_max*/4, EStossTime::BeforeFirstCard, /*b_announce_schneider_schwarz*/true)),
+        TRulesBoxClone::box_clone(rulessolo.upcast()),
+        /*n_stock*/0,
+    );
+    assert_eq!(game.schneider_schwarz_announceable(EPI1), None); // only playing party may announce
+    assert_eq!(game.schneider_schwarz_announceable(EPI0), Some(ESchneiderSchwarz::Schneider));
+    assert!(game.announce_schneider_schwarz(EPI1, ESchneiderSchwarz::Schneider).is_err());
+    unwrap!(game.announce_schneider_schwarz(EPI0, ESchneiderSchwarz::Schneider));
+    assert_eq!(game.schneider_schwarz_announceable(EPI0), Some(ESchneiderSchwarz::Schwarz));
+    assert!(game.announce_schneider_schwarz(EPI0, ESchneiderSchwarz::Schneider).is_err()); // already announced
+    unwrap!(game.zugeben(EO, EPI0));
+    assert_eq!(game.schneider_schwarz_announceable(EPI0), None); // too late
+    assert!(game.announce_schneider_schwarz(EPI0, ESchneiderSchwarz::Schwarz).is_err());
+}
+
+#[test]
+#[cfg(not(feature="dreier"))]
 fn test_stock() {
     use EPlayerIndex::*;
     let rulesrufspiel = rulesrufspiel_new_test(
@@ -1969,3 +2436,95 @@ fn test_stock() {
         );
     }
 }
+
+#[test]
+#[cfg(feature="dreier")]
+fn test_dreier() {
+    use EPlayerIndex::*;
+    test_rules_manual(
+        "0 wins Gras-Solo with Schneider",
+        sololike(EPlayerIndex::EPI0, EFarbe::Gras, ESoloLike::Solo, SPayoutDeciderPointBased::default_payoutdecider(/*n_payout_base*/50, /*n_payout_schneider_schwarz*/10, SLaufendeParams::new(10, 3))).upcast(),
+        vec![],
+        vec![],
+        /*n_stock*/0,
+        &[
+            (EPI0, [EO,SO,SU]),
+            (EPI0, [GO,EU,HU]),
+            (EPI0, [GA,HO,GU]),
+            (EPI1, [EZ,HK,EA]),
+            (EPI0, [HA,HZ,H9]),
+            (EPI0, [GZ,EK,SZ]),
+            (EPI0, [GK,E9,SK]),
+            (EPI0, [G9,SA,S9]),
+        ],
+        ([120, -60, -60], 0),
+    );
+    test_rules_manual(
+        "0 looses Ramsch",
+        &SRulesRamsch::new(10, VDurchmarsch::None, /*b_jungfrau*/false, /*on_price_stock*/None, /*b_schieben*/false),
+        vec![],
+        vec![],
+        /*n_stock*/0,
+        &[
+            (EPI0, [EO,HA,GA]),
+            (EPI0, [GO,HZ,GZ]),
+            (EPI0, [HO,HK,GK]),
+            (EPI0, [SO,H9,G9]),
+            (EPI0, [EU,EA,SA]),
+            (EPI0, [GU,EZ,SZ]),
+            (EPI0, [HU,EK,SK]),
+            (EPI0, [SU,E9,S9]),
+        ],
+        ([-20, 10, 10], 0),
+    );
+    test_rules_manual(
+        "0 wins Gras-Solo with Schneider, 1 gives Stoss, 2 doubled",
+        sololike(EPlayerIndex::EPI0, EFarbe::Gras, ESoloLike::Solo, SPayoutDeciderPointBased::default_payoutdecider(/*n_payout_base*/50, /*n_payout_schneider_schwarz*/10, SLaufendeParams::new(10, 3))).upcast(),
+        vec![2],
+        vec![1],
+        /*n_stock*/0,
+        &[
+            (EPI0, [EO,SO,SU]),
+            (EPI0, [GO,EU,HU]),
+            (EPI0, [GA,HO,GU]),
+            (EPI1, [EZ,HK,EA]),
+            (EPI0, [HA,HZ,H9]),
+            (EPI0, [GZ,EK,SZ]),
+            (EPI0, [GK,E9,SK]),
+            (EPI0, [G9,SA,S9]),
+        ],
+        ([480, -240, -240], 0),
+    );
+    test_rules_manual(
+        "0 wins Bettel",
+        &SRulesBettel::<SBettelAllAllowedCardsWithinStichNormal>::new(EPlayerIndex::EPI0, /*i_prio*/0, /*n_payout_base*/10, /*b_brett*/false),
+        vec![],
+        vec![],
+        /*n_stock*/0,
+        &[
+            (EPI0, [E9,EU,HU]),
+            (EPI1, [EA,HA,EZ]),
+            (EPI1, [EO,HO,G9]),
+            (EPI1, [EK,HK,GZ]),
+            (EPI1, [GU,SU,H9]),
+            (EPI1, [GO,SO,HZ]),
+            (EPI1, [GK,SK,S9]),
+            (EPI1, [GA,SA,SZ]),
+        ],
+        ([20, -10, -10], 0),
+    );
+    use crate::rules::ruleset::SRuleSet;
+    assert_eq!(unwrap!(SRuleSet::from_string("base-price=10\nsolo-price=50\nlauf-min=3\n[solo]")).ekurzlang, EKurzLang::Kurz);
+    assert!(SRuleSet::from_string("base-price=10\nsolo-price=50\nlauf-min=3\ndeck=\"lang\"\n[solo]").is_err());
+    assert!(SRuleSet::from_string("base-price=10\nsolo-price=50\nlauf-min=3\n[rufspiel]").is_err());
+}
+
+#[test]
+fn test_ruleset_players() {
+    let resruleset = crate::rules::ruleset::SRuleSet::from_file(std::path::Path::new("../rulesets/dreier.toml"));
+    if cfg!(feature="dreier") {
+        unwrap!(resruleset);
+    } else {
+        assert!(resruleset.is_err());
+    }
+}
@@ -63,12 +63,12 @@ plain_enum_mod!(moderelativeplayerposition, ERelativePlayerPosition {
 
 impl EPlayerIndex {
     fn to_relativeplayerposition(self, epi_myself: EPlayerIndex) -> ERelativePlayerPosition {
-        static_assert!(assert_eq(EPlayerIndex::SIZE, ERelativePlayerPosition::SIZE));
+        static_assert!(assert(EPlayerIndex::SIZE<=ERelativePlayerPosition::SIZE));
         match self.wrapped_difference(epi_myself).0 {
             EPlayerIndex::EPI0 => ERelativePlayerPosition::Myself,
             EPlayerIndex::EPI1 => ERelativePlayerPosition::Left,
-            EPlayerIndex::EPI2 => ERelativePlayerPosition::VisAVis,
-            EPlayerIndex::EPI3 => ERelativePlayerPosition::Right,
+            epi if epi.to_usize()==EPlayerIndex::SIZE-1 => ERelativePlayerPosition::Right,
+            _ => ERelativePlayerPosition::VisAVis,
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::game_analysis::*;
 use crate::game::*;
-use crate::rules::ruleset::{SStossParams, VStockOrT};
+use crate::rules::ruleset::{EStossTime, SStossParams, VStockOrT};
 use crate::primitives::*;
 use crate::primitives::cardvector::*;
 use crate::util::{*, parser::*};
@@ -17,6 +17,15 @@ pub fn subcommand(str_subcommand: &str) -> clap::App {
         .arg(clap::Arg::with_name("include-no-findings")
             .long("include-no-findings")
         )
+        .arg(clap::Arg::with_name("objective")
+            .long("objective")
+            .takes_value(true)
+            .possible_values(&["min", "parties"])
+        )
+        .arg(clap::Arg::with_name("ranking")
+            .long("ranking")
+            .takes_value(true) // min, expected, risk or risk:<factor>
+        )
 }
 
 #[derive(Debug)]
@@ -51,10 +60,8 @@ pub fn analyze_sauspiel_html(str_html: &str) -> Result<SGameResultGeneric<SSausp
     use select::{document::Document, node::Node, predicate::*};
     let doc = Document::from(str_html);
     fn vec_to_arr<T: std::fmt::Debug>(vect: Vec<T>) -> Result<[T; EPlayerIndex::SIZE], failure::Error> {
-        let (card0, card1, card2, card3) = vect.into_iter()
-            .collect_tuple()
-            .ok_or_else(|| format_err!("Wrong number of elements"))?;
-        Ok([card0, card1, card2, card3])
+        vect.try_into()
+            .map_err(|_vect| format_err!("Wrong number of elements"))
     }
     let mapepistr_username = vec_to_arr(
         doc.find(Class("game-participants"))
@@ -329,7 +336,7 @@ pub fn analyze_sauspiel_html(str_html: &str) -> Result<SGameResultGeneric<SSausp
         let mut game = SGameGeneric::new_with(
             aveccard,
             doublings,
-            /*ostossparams*/Some(SStossParams::new(/*n_stoss_max*/4)), // TODO? is this correct
+            /*ostossparams*/Some(SStossParams::new(/*n_stoss_max*/4, EStossTime::BeforeFirstCard, /*b_announce_schneider_schwarz*/false)), // TODO? is this correct
             rules,
             /*n_stock*/0, // Sauspiel does not support stock
             ruleset,
@@ -389,6 +396,7 @@ pub fn analyze_plain(str_lines: &str) -> impl Iterator<Item=Result<SGame, failur
 }
 
 #[test]
+#[cfg(not(feature="dreier"))]
 fn test_analyze_plain() {
     fn internal_test(str_in: &str) {
         unwrap!(unwrap!(analyze_plain(str_in).exactly_one()));
@@ -440,5 +448,7 @@ pub fn run(clapmatches: &clap::ArgMatches) -> Result<(), Error> {
         /*fn_link*/|str_description: &str| str_description.to_string(),
         vecgame.into_iter(),
         /*b_include_no_findings*/clapmatches.is_present("include-no-findings"),
+        /*b_objective_parties*/Some("parties")==clapmatches.value_of("objective"),
+        super::payout_ranking(clapmatches)?,
     )
 }
@@ -1,3 +1,4 @@
+use crate::ai;
 use crate::game::run::run_simple_game_loop;
 use crate::player::{*, playercomputer::*, playerhuman::*};
 use crate::primitives::*;
@@ -9,18 +10,39 @@ pub fn subcommand(str_subcommand: &str) -> clap::App {
     clap::SubCommand::with_name(str_subcommand)
         .about("Simulate players to play against")
         .arg(clap_arg("ruleset", "rulesets/default.toml"))
-        .arg(clap_arg("ai", "cheating"))
+        .args(&super::clap_args_ai())
         .arg(clap_arg("numgames", "4"))
+        .arg(clap::Arg::with_name("doubling-threshold")
+            .long("doubling-threshold")
+            .takes_value(true) // defaults to SDoublingParams::default()
+        )
+        .arg(clap::Arg::with_name("stoss-threshold")
+            .long("stoss-threshold")
+            .takes_value(true) // defaults to SStossDecisionParams::default()
+        )
 }
 
 pub fn run(clapmatches: &clap::ArgMatches) -> Result<(), Error> {
+    let f_doubling_threshold = clapmatches.value_of("doubling-threshold")
+        .map_or(Ok(ai::SDoublingParams::default().f_payout_threshold), str::parse::<f64>)?;
+    let f_stoss_threshold = clapmatches.value_of("stoss-threshold")
+        .map_or(Ok(ai::SStossDecisionParams::default().f_payout_threshold), str::parse::<f64>)?;
     let _tui = skui::STuiGuard::init_ui();
     let (mut aattable, n_stock) = run_simple_game_loop(
         /*aplayer*/EPlayerIndex::map_from_fn(|epi| -> Box<dyn TPlayer> {
             if EPlayerIndex::EPI1==epi {
                 Box::new(SPlayerHuman{ai : super::ai(clapmatches)})
             } else {
-                Box::new(SPlayerComputer{ai: super::ai(clapmatches)})
+                Box::new(SPlayerComputer{ai: super::ai(clapmatches)
+                    .with_doublingparams(ai::SDoublingParams{
+                        f_payout_threshold: f_doubling_threshold,
+                        ..ai::SDoublingParams::default()
+                    })
+                    .with_stossdecisionparams(ai::SStossDecisionParams{
+                        f_payout_threshold: f_stoss_threshold,
+                        ..ai::SStossDecisionParams::default()
+                    })
+                })
             }
         }),
         /*n_games*/unwrap!(clapmatches.value_of("numgames")).parse::<usize>().unwrap_or(4),
@@ -29,7 +51,12 @@ pub fn run(clapmatches: &clap::ArgMatches) -> Result<(), Error> {
     aattable.sort_unstable_by_key(|attable| attable.n_money);
     println!("Results:");
     for attable in aattable.iter() {
-        println!("{} {}", attable.player.name(), attable.n_money);
+        println!("{} {} (doubled {} games, {} in these games)",
+            attable.player.name(),
+            attable.n_money,
+            attable.n_games_doubled,
+            attable.n_money_doubled,
+        );
     }
     println!("Stock: {}", n_stock);
     Ok(())
@@ -38,7 +65,6 @@ pub fn run(clapmatches: &clap::ArgMatches) -> Result<(), Error> {
 #[test]
 fn test_game_loop() {
     use rand::prelude::IteratorRandom;
-    use crate::ai;
     use crate::player::{
         *,
         playercomputer::*,
@@ -53,6 +79,7 @@ fn test_game_loop() {
             [ // str_allowed_games
                 r"
                 [rufspiel]
+                [hochzeit]
                 [solo]
                 [wenz]
                 lauf-min=2
@@ -74,6 +101,28 @@ fn test_game_loop() {
                 [bettel]
                 stichzwang=true
                 ",
+                r"
+                [solo]
+                [bettel]
+                brett=true
+                brett-price=40
+                ober-unter-trumpf=true
+                ",
+                r"
+                [rufspiel]
+                ruf-zehn=true
+                davonlaufen=false
+                [solo]
+                ",
+                r"
+                [solo]
+                sie=false
+                [wenz]
+                tout-price=80
+                sie=true
+                [geier]
+                tout=false
+                ",
             ],
             [ // str_no_active_game
                 r"[ramsch]
@@ -85,6 +134,18 @@ fn test_game_loop() {
                 r#"[ramsch]
                 price=50
                 durchmarsch = "all""#,
+                r"[ramsch]
+                price=20
+                [stock]
+                price=10",
+                r"[ramsch]
+                price=10
+                jungfrau=true
+                schieben=true",
+                r"[kreuzspiel]",
+                r#"[kreuzspiel]
+                price=30
+                parties="alte""#,
                 r"[stock]",
                 r"[stock]
                 price=30",
@@ -101,9 +162,14 @@ fn test_game_loop() {
                 r"[stoss]
                 max=3
                 ",
+                r#"[stoss]
+                until="own-first-card""#,
+                r"[stoss]
+                announce-schneider-schwarz=true",
+                r"[der-alte-muss]",
             ]
         )
-            .map(|(n_base_price, n_solo_price, n_lauf_min, str_allowed_games, str_no_active_game, str_extras)| {
+            .filter_map(|(n_base_price, n_solo_price, n_lauf_min, str_allowed_games, str_no_active_game, str_extras)| {
                 let str_ruleset = format!(
                     "base-price={}
                     solo-price={}
@@ -114,7 +180,12 @@ fn test_game_loop() {
                     n_base_price, n_solo_price, n_lauf_min, str_allowed_games, str_no_active_game, str_extras
                 );
                 println!("{}", str_ruleset);
-                unwrap!(crate::rules::ruleset::SRuleSet::from_string(&str_ruleset))
+                let resruleset = crate::rules::ruleset::SRuleSet::from_string(&str_ruleset);
+                if cfg!(feature="dreier") {
+                    resruleset.ok() // e.g. Rufspiel or a long deck are not supported by three players
+                } else {
+                    Some(unwrap!(resruleset))
+                }
             })
             .choose_multiple(&mut rng, 2)
     {
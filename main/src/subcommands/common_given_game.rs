@@ -23,9 +23,13 @@ pub fn subcommand_given_game(str_subcommand: &'static str, str_about: &'static s
         .arg(single_arg("rules", "rules"))
         .arg(single_arg("hand", "hand"))
         .arg(single_arg("cards_on_table", "cards-on-table"))
+        .arg(clap::Arg::with_name("open_hand").long("open-hand").takes_value(true)) // hand revealed by the rules, e.g. in Bettel Brett
         .arg(clap::Arg::with_name("simulate_hands").long("simulate-hands").takes_value(true))
         .arg(clap::Arg::with_name("verbose").long("verbose").short("v"))
         .arg(clap::Arg::with_name("constrain_hands").long("constrain-hands").takes_value(true))
+        .arg(clap::Arg::with_name("constraint_macros").long("constraint-macros").takes_value(true)) // file with lines "name = constraint"
+        .arg(super::clap_arg_format())
+        .args(&super::clap_args_stakes())
 }
 
 pub trait TWithCommonArgs {
@@ -40,36 +44,129 @@ pub trait TWithCommonArgs {
     ) -> Result<(), Error>;
 }
 
+// rejects input that could not stem from an actual game, so that later stages need not deal with it
+fn check_given_game(rules: &dyn TRules, hand_fixed: &SHand, ohand_open: Option<&SHand>, slccard_as_played: &[SCard], ekurzlang: EKurzLang) -> Result<(SStichSequence, Option<(EPlayerIndex, SHand)>), Error> {
+    if hand_fixed.cards().is_empty() {
+        bail!("Hand must not be empty.");
+    }
+    let mut mapcardb_seen = SCard::map_from_fn(|_card| false);
+    for card in slccard_as_played.iter()
+        .chain(hand_fixed.cards().iter())
+        .chain(ohand_open.iter().flat_map(|hand_open| hand_open.cards().iter()))
+    {
+        if !ekurzlang.supports_card(*card) {
+            bail!("{} is not part of the deck ({:?}).", card, ekurzlang);
+        }
+        if mapcardb_seen[*card] {
+            bail!("{} given more than once.", card);
+        }
+        mapcardb_seen[*card] = true;
+    }
+    let stichseq = SStichSequence::new_from_cards(ekurzlang, slccard_as_played.iter().copied(), rules);
+    let epi_fixed = unwrap!(stichseq.current_stich().current_playerindex());
+    let otplepihand_open = match (
+        rules.playerindex_open_hand(&stichseq).filter(|epi_open| *epi_open!=epi_fixed),
+        ohand_open,
+    ) {
+        (None, None) => None,
+        (Some(epi_open), Some(hand_open)) => {
+            let n_cards_open = remaining_cards_per_hand(&stichseq)[epi_open];
+            if hand_open.cards().len()!=n_cards_open {
+                bail!("Open hand {} of player {} must consist of {} cards.", hand_open, epi_open, n_cards_open);
+            }
+            Some((epi_open, hand_open.clone()))
+        },
+        (Some(epi_open), None) => bail!("{} reveals the hand of player {}, so it must be given.", rules, epi_open),
+        (None, Some(hand_open)) => bail!("{} reveals no other player's hand, but {} was given.", rules, hand_open),
+    };
+    // cards a player holds at some point: cards played later plus, for epi_fixed and the open hand, the given hand
+    let hand_known = |i_card: usize, epi: EPlayerIndex| {
+        SHand::new_from_iter(
+            stichseq.visible_cards()
+                .skip(i_card)
+                .filter(|&(epi_card, _card)| epi_card==epi)
+                .map(|(_epi, card)| *card)
+                .chain(hand_fixed.cards().iter().copied().filter(|_card| epi==epi_fixed))
+                .chain(otplepihand_open.iter()
+                    .filter(|(epi_open, _hand_open)| *epi_open==epi)
+                    .flat_map(|(_epi_open, hand_open)| hand_open.cards().iter().copied())
+                )
+        )
+    };
+    if let Some(epi_active) = rules.playerindex().filter(|epi_active| *epi_active==epi_fixed) {
+        let hand_active = hand_known(0, epi_active);
+        if !rules.can_be_played(SFullHand::new(hand_active.cards(), ekurzlang)) {
+            bail!("{} cannot be played with {}.", rules, hand_active);
+        }
+    }
+    let veccard_unknown = SCard::values(ekurzlang)
+        .filter(|card| !mapcardb_seen[*card])
+        .collect::<Vec<_>>();
+    let mut stichseq_simulate = SStichSequence::new(ekurzlang);
+    for (i_card, (epi, card)) in stichseq.visible_cards().enumerate() {
+        let hand = hand_known(i_card, epi);
+        // other players' hands are only partially known: unknown cards of the same trumpf/farbe
+        // may justify the card (e.g. davonlaufen in Rufspiel)
+        if !rules.card_is_allowed(&stichseq_simulate, &hand, *card)
+            && (epi==epi_fixed || !rules.card_is_allowed(
+                &stichseq_simulate,
+                &SHand::new_from_iter(
+                    hand.cards().iter()
+                        .chain(veccard_unknown.iter()
+                            .filter(|card_unknown| rules.trumpforfarbe(**card_unknown)==rules.trumpforfarbe(*card))
+                            .take(remaining_cards_per_hand(&stichseq)[epi])
+                        )
+                        .copied()
+                ),
+                *card,
+            ))
+        {
+            bail!("Player {} could not have played {} while holding {}.", epi, card, hand);
+        }
+        stichseq_simulate.zugeben(*card, rules);
+    }
+    Ok((stichseq, otplepihand_open))
+}
+
+pub fn constraint_macros(clapmatches: &clap::ArgMatches) -> Result<SConstraintMacros, Error> {
+    clapmatches.value_of("constraint_macros").map_or(Ok(SConstraintMacros::default()), |str_path|
+        SConstraintMacros::from_file(std::path::Path::new(str_path))
+    )
+}
+
 pub fn with_common_args(
     clapmatches: &clap::ArgMatches,
     withcommanargs: impl TWithCommonArgs
 ) -> Result<(), Error> {
-    let b_verbose = clapmatches.is_present("verbose");
+    let b_verbose = clapmatches.is_present("verbose"); // verbose output goes to stderr to keep stdout parseable
     let hand_fixed = super::str_to_hand(unwrap!(clapmatches.value_of("hand")))?;
     let veccard_as_played = &cardvector::parse_cards::<Vec<_>>(
         unwrap!(clapmatches.value_of("cards_on_table")),
     ).ok_or_else(||format_err!("Could not parse played cards"))?;
-    // TODO check that everything is ok (no duplicate cards, cards are allowed, current stich not full, etc.)
     let rules = crate::rules::parser::parse_rule_description_simple(unwrap!(clapmatches.value_of("rules")))?;
     let rules = rules.as_ref();
-    let stichseq = SStichSequence::new_from_cards(
+    let ohand_open = clapmatches.value_of("open_hand").map(super::str_to_hand).transpose()?;
+    let (stichseq, otplepihand_open) = check_given_game(
+        rules,
+        &hand_fixed,
+        ohand_open.as_ref(),
+        veccard_as_played,
         /*ekurzlang*/EKurzLang::checked_from_cards_per_player(
             /*n_stichs_complete*/veccard_as_played.len() / EPlayerIndex::SIZE
                 + hand_fixed.cards().len()
         )
             .ok_or_else(|| format_err!("Cannot determine ekurzlang from {} and {:?}.", hand_fixed, veccard_as_played))?,
-        veccard_as_played.iter().copied(),
-        rules
-    );
+    )?;
     let determinebestcard =  SDetermineBestCard::new(
         rules,
         &stichseq,
         &hand_fixed,
     );
     let oconstraint = if_then_some!(let Some(str_constrain_hands)=clapmatches.value_of("constrain_hands"), {
-        let relation = str_constrain_hands.parse::<VConstraint>().map_err(|_|format_err!("Cannot parse hand constraints"))?;
+        let relation = constraint_macros(clapmatches)?.parse_constraint(str_constrain_hands)
+            .map_err(|err| format_err!("Cannot parse hand constraints: {}", err))?;
         if b_verbose {
-            println!("Constraint parsed as: {}", relation);
+            eprintln!("Constraint parsed as: {}", relation);
         }
         relation
     });
@@ -97,26 +194,65 @@ pub fn with_common_args(
     cartesian_match!(forward,
         match ((oiteratehands, eremainingcards)) {
             (Some(All), _)|(None, _1|_2|_3|_4) => (
-                all_possible_hands(&stichseq, hand_fixed.clone(), epi_fixed, rules)
+                all_possible_hands(&stichseq, hand_fixed.clone(), epi_fixed, otplepihand_open.clone(), rules)
                     .filter(|ahand| oconstraint.as_ref().map_or(true, |relation|
                         relation.eval(ahand, rules)
                     ))
             ),
             (Some(Sample(n_samples)), _) => (
-                forever_rand_hands(&stichseq, hand_fixed.clone(), epi_fixed, rules)
+                forever_rand_hands(&stichseq, hand_fixed.clone(), epi_fixed, otplepihand_open.clone(), rules)
                     .filter(|ahand| oconstraint.as_ref().map_or(true, |relation|
                         relation.eval(ahand, rules)
                     ))
                     .take(n_samples)
             ),
             (None, _5|_6|_7|_8) => (
-                forever_rand_hands(&stichseq, hand_fixed.clone(), epi_fixed, rules)
+                forever_rand_hands(&stichseq, hand_fixed.clone(), epi_fixed, otplepihand_open.clone(), rules)
                     .filter(|ahand| oconstraint.as_ref().map_or(true, |relation|
                         relation.eval(ahand, rules)
                     ))
-                    .take(/*n_suggest_card_samples*/50)
+                    .take(if clapmatches.is_present("max_duration") {
+                        usize::MAX // anytime search determines number of samples itself
+                    } else {
+                        /*n_suggest_card_samples*/50
+                    })
             ),
         },
     )
 }
 
+
+#[test]
+#[cfg(not(feature="dreier"))]
+fn test_check_given_game() {
+    let check_with_open_hand = |str_rules, str_hand, ostr_hand_open: Option<&str>, str_cards_on_table| {
+        let rules = unwrap!(crate::rules::parser::parse_rule_description_simple(str_rules));
+        let hand_fixed = unwrap!(super::str_to_hand(str_hand));
+        let ohand_open = ostr_hand_open.map(|str_hand_open| unwrap!(super::str_to_hand(str_hand_open)));
+        let veccard_as_played = unwrap!(cardvector::parse_cards::<Vec<_>>(str_cards_on_table));
+        check_given_game(
+            rules.as_ref(),
+            &hand_fixed,
+            ohand_open.as_ref(),
+            &veccard_as_played,
+            unwrap!(EKurzLang::checked_from_cards_per_player(
+                veccard_as_played.len() / EPlayerIndex::SIZE + hand_fixed.cards().len()
+            )),
+        )
+    };
+    let check = |str_rules, str_hand, str_cards_on_table| check_with_open_hand(str_rules, str_hand, None, str_cards_on_table);
+    assert!(check("Herz-Solo von 0", "eo go ho so", "ea e7 e8 e9 ga g7 g8 g9 sa s7 s8 s9 ha h7 h8 h9").is_ok());
+    // player 1 may be eichel-frei
+    assert!(check("Herz-Solo von 0", "eo go ho so eu gu hu su", "e7 h9").is_ok());
+    // duplicate card
+    assert!(check("Herz-Solo von 0", "eo go ho so eu gu hu e7", "e7 h9").is_err());
+    // caller must not hold the Rufsau
+    assert!(check("Rufspiel Eichel von 0", "ea go ho so eu gu hu su", "").is_err());
+    // player 1 trumped although he plays eichel later
+    assert!(check("Herz-Solo von 0", "eo go ho so eu gu hu", "e7 h9 e8 e9 ek").is_err());
+    // declarer's hand is open after the first stich in Bettel Brett
+    assert!(check_with_open_hand("Bettel Brett von 0", "ea ek eo eu ga gk go", Some("h7 h8 h9 hz hu ho hk"), "e7 e8 e9 ez").is_ok());
+    assert!(check_with_open_hand("Bettel Brett von 0", "ea ek eo eu ga gk go", None, "e7 e8 e9 ez").is_err());
+    assert!(check_with_open_hand("Bettel Brett von 0", "ea ek eo eu ga gk go", Some("h7 h8"), "e7 e8 e9 ez").is_err());
+    assert!(check_with_open_hand("Bettel von 0", "ea ek eo eu ga gk go", Some("h7 h8 h9 hz hu ho hk"), "e7 e8 e9 ez").is_err());
+}
@@ -2,6 +2,7 @@ use crate::ai::*;
 use crate::primitives::*;
 use crate::rules::*;
 use crate::util::*;
+use serde::Serialize;
 
 use super::common_given_game::*;
 
@@ -25,16 +26,21 @@ pub fn run(clapmatches: &clap::ArgMatches) -> Result<(), Error> {
             _b_verbose: bool,
         ) -> Result<(), Error> {
             let clapmatches = self.clapmatches;
+            let constraintmacros = constraint_macros(clapmatches)?;
             let vecconstraint = unwrap!(clapmatches.values_of("inspect"))
                 .map(|str_inspect| /*-> Result<_, Error>*/ {
-                    str_inspect.parse::<VConstraint>()
-                        .map_err(|_| format_err!("Cannot parse inspection target."))
+                    constraintmacros.parse_constraint(str_inspect)
+                        .map_err(|err| format_err!("Cannot parse inspection target: {}", err))
                 })
                 .collect::<Result<Vec<_>,_>>()?;
-            for constraint in vecconstraint.iter() {
-                println!("{:?}", constraint);
+            let b_json = super::format_json(clapmatches);
+            if !b_json {
+                for constraint in vecconstraint.iter() {
+                    println!("{:?}", constraint);
+                }
             }
-            #[derive(PartialOrd, Ord, Hash, PartialEq, Eq)]
+            #[derive(PartialOrd, Ord, Hash, PartialEq, Eq, Serialize)]
+            #[serde(untagged)]
             enum VInspectValue {
                 Usize(usize),
                 Bool(bool),
@@ -53,17 +59,38 @@ pub fn run(clapmatches: &clap::ArgMatches) -> Result<(), Error> {
             }
             let mut vectplvecinspectvaluen = mapvecinspectvaluen.into_iter().collect::<Vec<_>>();
             vectplvecinspectvaluen.sort_unstable_by(|lhs, rhs| lhs.0.cmp(&rhs.0));
-            for (vecinspectvalue, n_count) in vectplvecinspectvaluen {
-                for inspectvalue in vecinspectvalue {
-                    print!(
-                        "{} ",
-                        match inspectvalue {
-                            VInspectValue::Usize(n_val) => format!("{}", n_val),
-                            VInspectValue::Bool(b_val) => format!("{}", b_val),
-                        }
-                    );
+            if b_json {
+                #[derive(Serialize)]
+                struct SInspectCount {
+                    vecinspectvalue: Vec<VInspectValue>,
+                    n_count: usize,
+                }
+                #[derive(Serialize)]
+                struct SHandStatsOutput {
+                    str_rules: String,
+                    vecstr_inspect: Vec<String>,
+                    vecinspectcount: Vec<SInspectCount>,
+                }
+                println!("{}", serde_json::to_string(&SHandStatsOutput {
+                    str_rules: rules.to_string(),
+                    vecstr_inspect: vecconstraint.iter().map(VConstraint::to_string).collect(),
+                    vecinspectcount: vectplvecinspectvaluen.into_iter()
+                        .map(|(vecinspectvalue, n_count)| SInspectCount{vecinspectvalue, n_count})
+                        .collect(),
+                })?);
+            } else {
+                for (vecinspectvalue, n_count) in vectplvecinspectvaluen {
+                    for inspectvalue in vecinspectvalue {
+                        print!(
+                            "{} ",
+                            match inspectvalue {
+                                VInspectValue::Usize(n_val) => format!("{}", n_val),
+                                VInspectValue::Bool(b_val) => format!("{}", b_val),
+                            }
+                        );
+                    }
+                    println!("{}", n_count);
                 }
-                println!("{}", n_count);
             }
             Ok(())
         }
@@ -4,25 +4,32 @@ use crate::rules::*;
 use crate::cardvector::*;
 use combine::{char::*, *};
 
+// StrMacro: names of macros not yet replaced (String while parsing, Infallible once resolved)
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub enum VNumVal {
+pub enum VNumVal<StrMacro=std::convert::Infallible> {
     Const(usize),
     Card(SCard, EPlayerIndex),
     TrumpfOrFarbe(VTrumpfOrFarbe, EPlayerIndex),
     Schlag(ESchlag, EPlayerIndex),
+    Points(EPlayerIndex),
+    CalledCard(EPlayerIndex),
+    Laufende(EPlayerIndex),
+    Frei(VTrumpfOrFarbe, EPlayerIndex),
+    Sum(Box<VNumVal<StrMacro>>, Box<VNumVal<StrMacro>>),
+    Macro(StrMacro), // replaced by SConstraintMacros::parse_constraint
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub enum VConstraint {
-    Not(Box<VConstraint>),
-    Num(VNumVal),
+pub enum VConstraint<StrMacro=std::convert::Infallible> {
+    Not(Box<VConstraint<StrMacro>>),
+    Num(VNumVal<StrMacro>),
     Relation {
-        numval_lhs: VNumVal,
+        numval_lhs: VNumVal<StrMacro>,
         ord: std::cmp::Ordering,
-        numval_rhs: VNumVal,
+        numval_rhs: VNumVal<StrMacro>,
     },
-    Conjunction(Box<VConstraint>, Box<VConstraint>),
-    Disjunction(Box<VConstraint>, Box<VConstraint>),
+    Conjunction(Box<VConstraint<StrMacro>>, Box<VConstraint<StrMacro>>),
+    Disjunction(Box<VConstraint<StrMacro>>, Box<VConstraint<StrMacro>>),
 }
 
 impl VNumVal {
@@ -39,11 +46,55 @@ impl VNumVal {
             VNumVal::Schlag(eschlag, epi) => count(&ahand[*epi], |card|
                 card.schlag()==*eschlag
             ),
+            VNumVal::Points(epi) => ahand[*epi].cards().iter()
+                .map(|card| card_points::points_card(*card))
+                .sum::<isize>()
+                .as_num::<usize>(),
+            VNumVal::CalledCard(epi) => rules.called_card().map_or(0, |card_called|
+                count(&ahand[*epi], |card| *card==card_called)
+            ),
+            VNumVal::Laufende(epi) => {
+                let mut veccard_trumpf = SCard::values(EKurzLang::Lang)
+                    .filter(|card| VTrumpfOrFarbe::Trumpf==rules.trumpforfarbe(*card))
+                    .collect::<Vec<_>>();
+                veccard_trumpf.sort_unstable_by(|card_lhs, card_rhs| unwrap!(rules.compare_cards(*card_rhs, *card_lhs)));
+                veccard_trumpf.into_iter()
+                    .take_while(|card| ahand[*epi].contains(*card))
+                    .count()
+            },
+            VNumVal::Frei(trumpforfarbe, epi) => if 0==count(&ahand[*epi], |card|
+                trumpforfarbe==&rules.trumpforfarbe(*card)
+            ) {1} else {0},
+            VNumVal::Sum(numval_lhs, numval_rhs) => numval_lhs.eval(ahand, rules) + numval_rhs.eval(ahand, rules),
+            VNumVal::Macro(never) => match *never {},
         }
     }
 }
 
-impl std::fmt::Display for VNumVal {
+impl VNumVal<String> {
+    fn resolve_macros(self, constraintmacros: &SConstraintMacros) -> Result<VNumVal, Error> {
+        Ok(match self {
+            VNumVal::Const(n) => VNumVal::Const(n),
+            VNumVal::Card(card, epi) => VNumVal::Card(card, epi),
+            VNumVal::TrumpfOrFarbe(trumpforfarbe, epi) => VNumVal::TrumpfOrFarbe(trumpforfarbe, epi),
+            VNumVal::Schlag(eschlag, epi) => VNumVal::Schlag(eschlag, epi),
+            VNumVal::Points(epi) => VNumVal::Points(epi),
+            VNumVal::CalledCard(epi) => VNumVal::CalledCard(epi),
+            VNumVal::Laufende(epi) => VNumVal::Laufende(epi),
+            VNumVal::Frei(trumpforfarbe, epi) => VNumVal::Frei(trumpforfarbe, epi),
+            VNumVal::Sum(numval_lhs, numval_rhs) => VNumVal::Sum(
+                Box::new(numval_lhs.resolve_macros(constraintmacros)?),
+                Box::new(numval_rhs.resolve_macros(constraintmacros)?),
+            ),
+            VNumVal::Macro(str_macro) => match constraintmacros.get(&str_macro)? {
+                VConstraint::Num(numval) => numval.clone(),
+                constraint => bail!("Macro {} is not a number: {}", str_macro, constraint),
+            },
+        })
+    }
+}
+
+impl<StrMacro: std::fmt::Display> std::fmt::Display for VNumVal<StrMacro> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match self {
             VNumVal::Const(n) => write!(f, "{}", n),
@@ -53,6 +104,15 @@ impl std::fmt::Display for VNumVal {
                 VTrumpfOrFarbe::Farbe(efarbe) => write!(f, "{}({})", efarbe, epi),
             },
             VNumVal::Schlag(eschlag, epi) => write!(f, "{}({})", eschlag, epi),
+            VNumVal::Points(epi) => write!(f, "points({})", epi),
+            VNumVal::CalledCard(epi) => write!(f, "ruf({})", epi),
+            VNumVal::Laufende(epi) => write!(f, "laufende({})", epi),
+            VNumVal::Frei(trumpforfarbe, epi) => match trumpforfarbe {
+                VTrumpfOrFarbe::Trumpf => write!(f, "frei(t,{})", epi),
+                VTrumpfOrFarbe::Farbe(efarbe) => write!(f, "frei({},{})", efarbe, epi),
+            },
+            VNumVal::Sum(numval_lhs, numval_rhs) => write!(f, "{}+{}", numval_lhs, numval_rhs),
+            VNumVal::Macro(str_macro) => write!(f, "@{}", str_macro),
         }
     }
 }
@@ -78,7 +138,61 @@ impl VConstraint {
     }
 }
 
-impl std::fmt::Display for VConstraint {
+impl VConstraint<String> {
+    fn resolve_macros(self, constraintmacros: &SConstraintMacros) -> Result<VConstraint, Error> {
+        let resolve_box = |constraint: Box<VConstraint<String>>| -> Result<_, Error> {
+            Ok(Box::new(constraint.resolve_macros(constraintmacros)?))
+        };
+        Ok(match self {
+            VConstraint::Num(VNumVal::Macro(str_macro)) => constraintmacros.get(&str_macro)?.clone(),
+            VConstraint::Not(constraint) => VConstraint::Not(resolve_box(constraint)?),
+            VConstraint::Num(numval) => VConstraint::Num(numval.resolve_macros(constraintmacros)?),
+            VConstraint::Relation{numval_lhs, ord, numval_rhs} => VConstraint::Relation{
+                numval_lhs: numval_lhs.resolve_macros(constraintmacros)?,
+                ord,
+                numval_rhs: numval_rhs.resolve_macros(constraintmacros)?,
+            },
+            VConstraint::Conjunction(constraint_lhs, constraint_rhs) => VConstraint::Conjunction(resolve_box(constraint_lhs)?, resolve_box(constraint_rhs)?),
+            VConstraint::Disjunction(constraint_lhs, constraint_rhs) => VConstraint::Disjunction(resolve_box(constraint_lhs)?, resolve_box(constraint_rhs)?),
+        })
+    }
+}
+
+// named constraints, given as lines "name = constraint" and referred to as "@name"
+#[derive(Default, Debug)]
+pub struct SConstraintMacros {
+    mapstrconstraint: std::collections::HashMap<String, VConstraint>,
+}
+
+impl SConstraintMacros {
+    pub fn from_string(str_macros: &str) -> Result<Self, Error> {
+        let mut constraintmacros = Self::default();
+        for str_line in str_macros.lines().map(str::trim).filter(|str_line| !str_line.is_empty() && !str_line.starts_with('#')) {
+            let (str_name, str_constraint) = str_line.split_once('=')
+                .ok_or_else(|| format_err!("Expected name = constraint: {}", str_line))?;
+            // macros may refer to previously defined macros
+            let constraint = constraintmacros.parse_constraint(str_constraint)?;
+            constraintmacros.mapstrconstraint.insert(str_name.trim().to_string(), constraint);
+        }
+        Ok(constraintmacros)
+    }
+
+    pub fn from_file(path: &std::path::Path) -> Result<Self, Error> {
+        use std::io::Read;
+        Self::from_string(&via_out_param_result(|str_macros| std::fs::File::open(path)?.read_to_string(str_macros))?.0)
+    }
+
+    fn get(&self, str_macro: &str) -> Result<&VConstraint, Error> {
+        self.mapstrconstraint.get(str_macro).ok_or_else(|| format_err!("Unknown macro {}", str_macro))
+    }
+
+    pub fn parse_constraint(&self, str_constraint: &str) -> Result<VConstraint, Error> {
+        parse_trimmed(str_constraint, "constraint", constraint_parser())?
+            .resolve_macros(self)
+    }
+}
+
+impl<StrMacro: std::fmt::Display> std::fmt::Display for VConstraint<StrMacro> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match self {
             VConstraint::Not(constraint) => write!(f, "!({})", constraint),
@@ -98,38 +212,55 @@ impl std::fmt::Display for VConstraint {
     }
 }
 
-fn numval_parser<I: Stream<Item=char>>() -> impl Parser<Input = I, Output = VNumVal>
+fn numval_parser<I: Stream<Item=char>>() -> impl Parser<Input = I, Output = VNumVal<String>>
     where I::Error: ParseError<I::Item, I::Range, I::Position>, // Necessary due to rust-lang/rust#24159
 {
+    fn epi_digit_parser<I: Stream<Item=char>>() -> impl Parser<Input = I, Output = EPlayerIndex>
+        where I::Error: ParseError<I::Item, I::Range, I::Position>, // Necessary due to rust-lang/rust#24159
+    {
+        satisfy_map(|chr: char| chr.to_digit(10)
+            .and_then(|n_epi| EPlayerIndex::checked_from_usize(n_epi.as_num::<usize>()))
+        )
+    }
     pub fn epi_parser<I: Stream<Item=char>>() -> impl Parser<Input = I, Output = EPlayerIndex>
         where I::Error: ParseError<I::Item, I::Range, I::Position>, // Necessary due to rust-lang/rust#24159
     {
         (spaces(), char('('), spaces())
-            .with(choice!(
-                char('0').map(|_chr| EPlayerIndex::EPI0),
-                char('1').map(|_chr| EPlayerIndex::EPI1),
-                char('2').map(|_chr| EPlayerIndex::EPI2),
-                char('3').map(|_chr| EPlayerIndex::EPI3)
-            ))
+            .with(epi_digit_parser())
             .skip((spaces(), char(')'), spaces()))
     }
-    choice!(
+    fn trumpforfarbe_parser<I: Stream<Item=char>>() -> impl Parser<Input = I, Output = VTrumpfOrFarbe>
+        where I::Error: ParseError<I::Item, I::Range, I::Position>, // Necessary due to rust-lang/rust#24159
+    {
+        choice!(
+            choice!(char('t'), char('T')).map(|_| VTrumpfOrFarbe::Trumpf),
+            farbe_parser().map(VTrumpfOrFarbe::Farbe)
+        )
+    }
+    let single_numval_parser = choice!(
         attempt((card_parser(), epi_parser()).map(|(card, epi)| VNumVal::Card(card, epi))),
-        (
-            choice!(
-                choice!(char('t'), char('T')).map(|_| VTrumpfOrFarbe::Trumpf),
-                farbe_parser().map(VTrumpfOrFarbe::Farbe)
-            ),
-            epi_parser()
-        ).map(|(trumpforfarbe, epi)| VNumVal::TrumpfOrFarbe(trumpforfarbe, epi)),
+        (trumpforfarbe_parser(), epi_parser()).map(|(trumpforfarbe, epi)| VNumVal::TrumpfOrFarbe(trumpforfarbe, epi)),
         attempt((schlag_parser(), epi_parser()).map(|(eschlag, epi)| VNumVal::Schlag(eschlag, epi))),
+        attempt(string("points").with(epi_parser()).map(VNumVal::Points)),
+        attempt(string("ruf").with(epi_parser()).map(VNumVal::CalledCard)),
+        attempt(string("laufende").with(epi_parser()).map(VNumVal::Laufende)),
+        attempt((string("frei"), spaces(), char('('), spaces())
+            .with((trumpforfarbe_parser(), (spaces(), char(','), spaces()).with(epi_digit_parser())))
+            .skip((spaces(), char(')'), spaces()))
+            .map(|(trumpforfarbe, epi)| VNumVal::Frei(trumpforfarbe, epi))
+        ),
+        char('@').with(many1(satisfy(|chr: char| chr.is_alphanumeric() || chr=='_'))).map(VNumVal::Macro),
         (many1(digit())./*TODO use and_then and get rid of unwrap*/map(|string: /*TODO String needed?*/String|
             unwrap!(string.parse::<usize>())
         )).map(VNumVal::Const)
-    )
+    );
+    sep_by1::<Vec<_>, _, _>(single_numval_parser, attempt((spaces(), char('+'), spaces())))
+        .map(|vecnumval| unwrap!(vecnumval.into_iter().reduce(|numval_lhs, numval_rhs|
+            VNumVal::Sum(Box::new(numval_lhs), Box::new(numval_rhs))
+        )))
 }
 
-fn single_constraint_parser_<I: Stream<Item=char>>() -> impl Parser<Input = I, Output = VConstraint>
+fn single_constraint_parser_<I: Stream<Item=char>>() -> impl Parser<Input = I, Output = VConstraint<String>>
     where I::Error: ParseError<I::Item, I::Range, I::Position>, // Necessary due to rust-lang/rust#24159
 {
     choice!(
@@ -155,14 +286,14 @@ fn single_constraint_parser_<I: Stream<Item=char>>() -> impl Parser<Input = I, O
     )
 }
 parser!(
-    fn single_constraint_parser[I]()(I) -> VConstraint
+    fn single_constraint_parser[I]()(I) -> VConstraint<String>
         where [I: Stream<Item = char>]
     {
         single_constraint_parser_()
     }
 );
 
-fn constraint_parser_<I: Stream<Item=char>>() -> impl Parser<Input = I, Output = VConstraint>
+fn constraint_parser_<I: Stream<Item=char>>() -> impl Parser<Input = I, Output = VConstraint<String>>
     where I::Error: ParseError<I::Item, I::Range, I::Position>, // Necessary due to rust-lang/rust#24159
 {
     macro_rules! make_bin_op_parser{($parser:ident, $chr:expr, $op:ident) => {
@@ -177,7 +308,7 @@ fn constraint_parser_<I: Stream<Item=char>>() -> impl Parser<Input = I, Output =
 }
 
 parser!(
-    fn constraint_parser[I]()(I) -> VConstraint
+    fn constraint_parser[I]()(I) -> VConstraint<String>
         where [I: Stream<Item = char>]
     {
         constraint_parser_()
@@ -185,6 +316,7 @@ parser!(
 );
 
 #[test]
+#[cfg(not(feature="dreier"))]
 fn test_constraint_parser() {
     fn test_internal(str_in: &str, constraint: VConstraint) {
         assert_eq!(unwrap!(str_in.parse::<VConstraint>()), constraint);
@@ -234,13 +366,46 @@ fn test_constraint_parser() {
             Box::new(Num(TrumpfOrFarbe(Farbe(Eichel), EPI2))),
         )
     );
-    // TODO more tests
+    test_simple_greater_0("points(1)", Points(EPI1));
+    test_simple_greater_0("ruf(2)", CalledCard(EPI2));
+    test_simple_greater_0("laufende(0)", Laufende(EPI0));
+    test_simple_greater_0("frei(e,1)", Frei(Farbe(Eichel), EPI1));
+    test_simple_greater_0("frei(t,3)", Frei(Trumpf, EPI3));
+    test_comparison(
+        "t(1)+t(3)>5",
+        Sum(Box::new(TrumpfOrFarbe(Trumpf, EPI1)), Box::new(TrumpfOrFarbe(Trumpf, EPI3))),
+        Greater,
+        Const(5),
+    );
+    for (str_in, str_out) in [
+        ("points(0)", "points(0)"),
+        ("ruf(1)", "ruf(1)"),
+        ("laufende(2)", "laufende(2)"),
+        ("frei(h,3)", "frei(Herz,3)"),
+        ("frei(t,0)", "frei(t,0)"),
+        ("t(1)+t(3)+s(2)", "t(1)+t(3)+Schelln(2)"),
+    ] {
+        assert_eq!(unwrap!(str_in.parse::<VConstraint>()).to_string(), str_out);
+    }
+    let constraintmacros = unwrap!(SConstraintMacros::from_string(
+        "# trumps of the opponents\ntrumpf13 = t(1)+t(3)\nstrong = @trumpf13>5\n",
+    ));
+    assert_eq!(
+        unwrap!(constraintmacros.parse_constraint("@strong&e(0)")),
+        unwrap!("t(1)+t(3)>5&e(0)".parse::<VConstraint>()),
+    );
+    assert_eq!(
+        unwrap!(constraintmacros.parse_constraint("@trumpf13<points(0)")),
+        unwrap!("t(1)+t(3)<points(0)".parse::<VConstraint>()),
+    );
+    assert!(constraintmacros.parse_constraint("@unknown>2").is_err());
+    assert!("@trumpf13>5".parse::<VConstraint>().is_err());
 }
 
 impl std::str::FromStr for VConstraint {
     type Err = Error;
     fn from_str(str_in: &str) -> Result<Self, Self::Err> {
-        parse_trimmed(str_in, "constraint", constraint_parser())
+        SConstraintMacros::default().parse_constraint(str_in)
     }
 }
 
@@ -23,11 +23,50 @@ fn clap_arg(str_long: &'static str, str_default: &'static str) -> clap::Arg<'sta
         .default_value(str_default)
 }
 
+fn clap_arg_format() -> clap::Arg<'static, 'static> {
+    clap_arg("format", "text")
+        .possible_values(&["text", "json"])
+}
+
+fn format_json(clapmatches: &clap::ArgMatches) -> bool {
+    Some("json")==clapmatches.value_of("format")
+}
+
+fn clap_args_ai() -> [clap::Arg<'static, 'static>; 3] {
+    [
+        clap_arg("ai", "cheating"),
+        clap_arg("ismcts-iterations", "10000"),
+        clap_arg("ai-duration-ms", "2000"), // time budget of time-limited AIs
+    ]
+}
+
+fn clap_args_stakes() -> [clap::Arg<'static, 'static>; 3] {
+    [
+        clap_arg("stoss", "0"),
+        clap_arg("doublings", "0"),
+        clap_arg("stock", "0"),
+    ]
+}
+
+fn stakes(clapmatches: &clap::ArgMatches) -> Result<(/*tpln_stoss_doubling*/(usize, usize), /*n_stock*/isize), Error> {
+    use clap::value_t;
+    Ok((
+        (value_t!(clapmatches, "stoss", usize)?, value_t!(clapmatches, "doublings", usize)?),
+        value_t!(clapmatches, "stock", isize)?,
+    ))
+}
+
+fn payout_ranking(clapmatches: &clap::ArgMatches) -> Result<crate::ai::VPayoutRanking, Error> {
+    clapmatches.value_of("ranking").map_or(Ok(crate::ai::VPayoutRanking::MinThenAvg), str::parse)
+}
+
 pub fn get_ruleset(clapmatches: &clap::ArgMatches) -> Result<SRuleSet, Error> {
     SRuleSet::from_file(std::path::Path::new(unwrap!(clapmatches.value_of("ruleset"))))
 }
 
 pub fn ai(subcommand_matches: &clap::ArgMatches) -> SAi {
+    use clap::value_t_or_exit;
+    let duration = || std::time::Duration::from_millis(value_t_or_exit!(subcommand_matches, "ai-duration-ms", u64));
     match unwrap!(subcommand_matches.value_of("ai")) {
         "cheating" => SAi::new_cheating(/*n_rank_rules_samples*/50, /*n_suggest_card_branches*/2),
         "simulating" => 
@@ -36,6 +75,17 @@ pub fn ai(subcommand_matches: &clap::ArgMatches) -> SAi {
                 /*n_suggest_card_branches*/2,
                 /*n_suggest_card_samples*/10,
             ),
+        "ismcts" =>
+            SAi::new_ismcts(
+                /*n_rank_rules_samples*/50,
+                /*n_iterations*/value_t_or_exit!(subcommand_matches, "ismcts-iterations", usize),
+                duration(),
+            ),
+        "anytime" =>
+            SAi::new_anytime(
+                /*n_rank_rules_samples*/50,
+                duration(),
+            ),
         _ => {
             println!("Warning: AI not recognized. Defaulting to 'cheating'");
             SAi::new_cheating(/*n_rank_rules_samples*/50, /*n_suggest_card_branches*/2)
@@ -1,15 +1,18 @@
 use crate::primitives::*;
 use crate::rules::ruleset::*;
 use crate::util::*;
+use serde::Serialize;
 
 pub fn subcommand(str_subcommand: &str) -> clap::App {
     use super::clap_arg;
     clap::SubCommand::with_name(str_subcommand)
         .about("Estimate strength of own hand")
         .arg(clap_arg("ruleset", "rulesets/default.toml"))
-        .arg(clap_arg("ai", "cheating"))
+        .args(&super::clap_args_ai())
         .arg(clap_arg("hand", ""))
         .arg(clap_arg("position", "0"))
+        .arg(super::clap_arg_format())
+        .args(&super::clap_args_stakes())
         // TODO align arguments with suggest-card
 }
 
@@ -21,7 +24,11 @@ pub fn run(clapmatches: &clap::ArgMatches) -> Result<(), Error> {
     use clap::value_t;
     let epi = value_t!(clapmatches.value_of("position"), EPlayerIndex).unwrap_or(EPlayerIndex::EPI0);
     let ai = super::ai(clapmatches);
-    println!("Hand: {}", SDisplayCardSlice(hand.get()));
+    let (tpln_stoss_doubling, n_stock) = super::stakes(clapmatches)?;
+    let b_json = super::format_json(clapmatches);
+    if !b_json {
+        println!("Hand: {}", SDisplayCardSlice(hand.get()));
+    }
     let mut vectplrulesf = allowed_rules(&ruleset.avecrulegroup[epi], hand)
         .filter_map(|orules| orules.map(|rules| { // do not rank None
             (
@@ -30,15 +37,39 @@ pub fn run(clapmatches: &clap::ArgMatches) -> Result<(), Error> {
                     hand,
                     epi,
                     rules.upcast(),
-                    /*tpln_stoss_doubling*/(0, 0), // assume no stoss, no doublings in subcommand rank-rules
-                    /*n_stock*/0, // assume no stock in subcommand rank-rules
+                    tpln_stoss_doubling,
+                    n_stock,
                 ),
             )
         }))
         .collect::<Vec<_>>();
     vectplrulesf.sort_unstable_by(|tplrulesf_lhs, tplrulesf_rhs| unwrap!(tplrulesf_rhs.1.partial_cmp(&tplrulesf_lhs.1)));
-    for (rules, f_avg_payout) in vectplrulesf {
-        println!("{}: {}", rules, f_avg_payout);
+    if b_json {
+        #[derive(Serialize)]
+        struct SRankedRules {
+            str_rules: String,
+            f_avg_payout: f64,
+        }
+        #[derive(Serialize)]
+        struct SRankRulesOutput {
+            str_hand: String,
+            epi: EPlayerIndex,
+            vecrankedrules: Vec<SRankedRules>,
+        }
+        println!("{}", serde_json::to_string(&SRankRulesOutput {
+            str_hand: SDisplayCardSlice(hand.get()).to_string(),
+            epi,
+            vecrankedrules: vectplrulesf.into_iter()
+                .map(|(rules, f_avg_payout)| SRankedRules {
+                    str_rules: rules.to_string(),
+                    f_avg_payout,
+                })
+                .collect(),
+        })?);
+    } else {
+        for (rules, f_avg_payout) in vectplrulesf {
+            println!("{}: {}", rules, f_avg_payout);
+        }
     }
     Ok(())
 }
@@ -4,7 +4,8 @@ use crate::util::*;
 use crate::rules::*;
 use itertools::*;
 
-use super::common_given_game::*;
+use super::{common_given_game::*, format_json, payout_ranking, stakes};
+use serde::Serialize;
 
 pub fn subcommand(str_subcommand: &'static str) -> clap::App {
     subcommand_given_game(str_subcommand, "Suggest a card to play given the game so far")
@@ -12,7 +13,9 @@ pub fn subcommand(str_subcommand: &'static str) -> clap::App {
         .arg(clap::Arg::with_name("branching").long("branching").takes_value(true))
         .arg(clap::Arg::with_name("prune").long("prune").takes_value(true))
         .arg(clap::Arg::with_name("visualize").long("visualize").takes_value(true))
-        // TODO Add possibility to request analysis by points/stichs
+        .arg(clap::Arg::with_name("max_duration").long("max-duration").takes_value(true)) // seconds, enables anytime search
+        .arg(clap::Arg::with_name("objective").long("objective").takes_value(true).possible_values(&["min", "parties", "points", "stichs", "schneiderfrei", "schneider"]))
+        .arg(clap::Arg::with_name("ranking").long("ranking").takes_value(true)) // min, expected, risk or risk:<factor>
 }
 
 pub fn run(clapmatches: &clap::ArgMatches) -> Result<(), Error> {
@@ -31,15 +34,55 @@ pub fn run(clapmatches: &clap::ArgMatches) -> Result<(), Error> {
         ) -> Result<(), Error> {
             let clapmatches = self.clapmatches;
             let epi_fixed = determinebestcard.epi_fixed;
-            let determinebestcardresult = { // we are interested in payout => single-card-optimization useless
-                macro_rules! forward{(($func_filter_allowed_cards: expr), ($foreachsnapshot: ident), $fn_visualizer: expr,) => {{ // TODORUST generic closures
+            let b_objective_parties = Some("parties")==clapmatches.value_of("objective");
+            let opointstichobjective = match clapmatches.value_of("objective") {
+                Some("points") => Some(VPointStichObjective::Points),
+                Some("stichs") => Some(VPointStichObjective::Stichs),
+                Some("schneiderfrei") => Some(VPointStichObjective::PointsAtLeast(31)),
+                Some("schneider") => Some(VPointStichObjective::PointsAtLeast(91)),
+                _ => None,
+            };
+            // alpha-beta pruning (also used for parties) computes only t_min
+            let b_only_t_min = opointstichobjective.is_none()
+                && (b_objective_parties || Some("alphabeta")==clapmatches.value_of("prune"));
+            let payoutranking = payout_ranking(clapmatches)?;
+            let b_json = format_json(clapmatches);
+            let (tpln_stoss_doubling, n_stock) = stakes(clapmatches)?;
+            let determinebestcardresult = if let Some(str_duration) = clapmatches.value_of("max_duration") {
+                macro_rules! forward{(($foreachsnapshot: expr),) => {{ // TODORUST generic closures
+                    determine_best_card_anytime(
+                        &determinebestcard,
+                        itahand,
+                        &$foreachsnapshot,
+                        std::time::Instant::now() + std::time::Duration::from_secs_f64(str_duration.parse()?),
+                        std::time::Instant::now,
+                    )
+                }}}
+                let determinebestcardanytimeresult = cartesian_match!(
+                    forward,
+                    match ((opointstichobjective, b_objective_parties)) {
+                        (Some(pointstichobjective), _) => (SMaxReachablePointStich::new(rules, epi_fixed, pointstichobjective)),
+                        (None, true) => (SPartyAwarePayout::new(rules, epi_fixed, tpln_stoss_doubling, n_stock)),
+                        (None, false) => (SMinReachablePayoutLowerBoundViaHint::new(rules, epi_fixed, tpln_stoss_doubling, n_stock)),
+                    },
+                );
+                if !b_json {
+                    println!("Hands: {}, branching: {}{}",
+                        determinebestcardanytimeresult.n_hands,
+                        determinebestcardanytimeresult.n_branches,
+                        if determinebestcardanytimeresult.n_branches < hand_fixed.cards().len() {""} else {" (unrestricted)"},
+                    );
+                }
+                determinebestcardanytimeresult.determinebestcardresult
+            } else { // we are interested in payout => single-card-optimization useless
+                macro_rules! forward{(($func_filter_allowed_cards: expr), ($foreachsnapshot: expr), $fn_visualizer: expr,) => {{ // TODORUST generic closures
                     let n_repeat_hand = clapmatches.value_of("repeat_hands").unwrap_or("1").parse()?;
                     determine_best_card(
                         &determinebestcard,
                         itahand
                             .inspect(|ahand| {
                                 if b_verbose { // TODO? dispatch statically
-                                    println!("{}", ahand.iter().join(" | "));
+                                    eprintln!("{}", ahand.iter().join(" | "));
                                 }
                             })
                             .flat_map(|ahand| {
@@ -49,12 +92,7 @@ pub fn run(clapmatches: &clap::ArgMatches) -> Result<(), Error> {
                                 )
                             }),
                         $func_filter_allowed_cards,
-                        &$foreachsnapshot::new(
-                            rules,
-                            epi_fixed,
-                            /*tpln_stoss_doubling*/(0, 0), // TODO? make customizable
-                            /*n_stock*/0, // TODO? make customizable
-                        ),
+                        &$foreachsnapshot,
                         $fn_visualizer,
                     )
                 }}}
@@ -70,7 +108,7 @@ pub fn run(clapmatches: &clap::ArgMatches) -> Result<(), Error> {
                             let (n_lo, n_hi) = (str_lo.trim().parse::<usize>()?, str_hi.trim().parse::<usize>()?);
                             if_then_some!(n_lo < hand_fixed.cards().len(), {
                                 if b_verbose {
-                                    println!("Branching bounds are large enough to eliminate branching factor.");
+                                    eprintln!("Branching bounds are large enough to eliminate branching factor.");
                                 }
                                 (n_lo, n_hi)
                             })
@@ -84,9 +122,12 @@ pub fn run(clapmatches: &clap::ArgMatches) -> Result<(), Error> {
                         })),
                         (None,_5|_6|_7|_8) => (&branching_factor(|_stichseq| (1, 3))),
                     },
-                    match ((clapmatches.value_of("prune"), eremainingcards)) {
-                        (Some("none"),_)|(_, _1|_2|_3) => (SMinReachablePayout),
-                        (Some("hint"),_)|(_, _4|_5|_6|_7|_8) => (SMinReachablePayoutLowerBoundViaHint),
+                    match ((opointstichobjective, b_objective_parties, clapmatches.value_of("prune"), eremainingcards)) {
+                        (Some(pointstichobjective),_,_,_) => (SMaxReachablePointStich::new(rules, epi_fixed, pointstichobjective)),
+                        (None,true,_,_) => (SPartyAwarePayout::new(rules, epi_fixed, tpln_stoss_doubling, n_stock)), // uses alpha-beta pruning
+                        (None,false,Some("alphabeta"),_) => (SMinReachablePayoutAlphaBeta::new(rules, epi_fixed, tpln_stoss_doubling, n_stock)),
+                        (None,false,Some("none"),_)|(None,false,_, _1|_2|_3) => (SMinReachablePayout::new(rules, epi_fixed, tpln_stoss_doubling, n_stock)),
+                        (None,false,Some("hint"),_)|(None,false,_, _4|_5|_6|_7|_8) => (SMinReachablePayoutLowerBoundViaHint::new(rules, epi_fixed, tpln_stoss_doubling, n_stock)),
                     },
                     match (clapmatches.value_of("visualize")) {
                         None => (|_,_,_| SNoVisualization),
@@ -102,44 +143,81 @@ pub fn run(clapmatches: &clap::ArgMatches) -> Result<(), Error> {
             };
             // TODO interface should probably output payout interval per card
             let mut veccardminmax = determinebestcardresult.cards_and_ts().collect::<Vec<_>>();
-            veccardminmax.sort_unstable_by_key(|&(_card, minmax)| minmax);
+            veccardminmax.sort_unstable_by(|(_card_lhs, minmax_lhs), (_card_rhs, minmax_rhs)| minmax_lhs.compare(minmax_rhs, payoutranking));
             veccardminmax.reverse(); // descending
+            if b_json {
+                #[derive(Serialize)]
+                struct SPayoutStatsOutput {
+                    n_min: isize,
+                    f_avg: f32,
+                    n_max: isize,
+                }
+                #[derive(Serialize)]
+                struct SPerMinMaxStrategyOutput {
+                    t_min: SPayoutStatsOutput,
+                    #[serde(skip_serializing_if = "Option::is_none")]
+                    t_selfish_min: Option<SPayoutStatsOutput>,
+                    #[serde(skip_serializing_if = "Option::is_none")]
+                    t_selfish_max: Option<SPayoutStatsOutput>,
+                    #[serde(skip_serializing_if = "Option::is_none")]
+                    t_max: Option<SPayoutStatsOutput>,
+                }
+                #[derive(Serialize)]
+                struct SSuggestedCard {
+                    str_card: String,
+                    n_samples: usize,
+                    perminmaxstrategy: SPerMinMaxStrategyOutput,
+                }
+                #[derive(Serialize)]
+                struct SSuggestCardOutput {
+                    str_rules: String,
+                    str_hand: String,
+                    epi: EPlayerIndex,
+                    vecsuggestedcard: Vec<SSuggestedCard>,
+                }
+                let payoutstatsoutput = |paystats: &SPayoutStats| SPayoutStatsOutput {
+                    n_min: paystats.min(),
+                    f_avg: paystats.avg(),
+                    n_max: paystats.max(),
+                };
+                println!("{}", serde_json::to_string(&SSuggestCardOutput {
+                    str_rules: rules.to_string(),
+                    str_hand: hand_fixed.to_string(),
+                    epi: epi_fixed,
+                    vecsuggestedcard: veccardminmax.into_iter()
+                        .map(|(card, minmax)| SSuggestedCard {
+                            str_card: card.to_string(),
+                            n_samples: minmax.t_min.count(),
+                            perminmaxstrategy: SPerMinMaxStrategyOutput {
+                                t_min: payoutstatsoutput(&minmax.t_min),
+                                t_selfish_min: if_then_some!(!b_only_t_min, payoutstatsoutput(&minmax.t_selfish_min)),
+                                t_selfish_max: if_then_some!(!b_only_t_min, payoutstatsoutput(&minmax.t_selfish_max)),
+                                t_max: if_then_some!(!b_only_t_min, payoutstatsoutput(&minmax.t_max)),
+                            },
+                        })
+                        .collect(),
+                })?);
+                return Ok(());
+            }
             // crude formatting: treat all numbers as f32, and convert structured input to a plain number table
-            const N_COLUMNS : usize = 12;
+            // only t_min (min, avg, max) if the other strategies are not computed
+            let n_columns = if b_only_t_min {3} else {12};
             let mut vecaf = Vec::new();
             let mut veclinestrings : Vec<(/*card*/String, /*numbers*/_)> = Vec::new();
-            let mut an_width = [0; N_COLUMNS];
-            let mut af_min = [f32::MAX; N_COLUMNS];
-            let mut af_max = [f32::MIN; N_COLUMNS];
+            let mut an_width = vec![0; n_columns];
+            let mut af_min = vec![f32::MAX; n_columns];
+            let mut af_max = vec![f32::MIN; n_columns];
             for (card, minmax) in veccardminmax {
-                let af = [
-                    minmax.t_min.min().as_num::<f32>(),
-                    minmax.t_min.avg(),
-                    minmax.t_min.max().as_num::<f32>(),
-                    minmax.t_selfish_min.min().as_num::<f32>(),
-                    minmax.t_selfish_min.avg(),
-                    minmax.t_selfish_min.max().as_num::<f32>(),
-                    minmax.t_selfish_max.min().as_num::<f32>(),
-                    minmax.t_selfish_max.avg(),
-                    minmax.t_selfish_max.max().as_num::<f32>(),
-                    minmax.t_max.min().as_num::<f32>(),
-                    minmax.t_max.avg(),
-                    minmax.t_max.max().as_num::<f32>(),
-                ];
-                let astr = [
-                    format!("{} ", af[0]),
-                    format!("{:.2} ", af[1]),
-                    format!("{} ", af[2]),
-                    format!("{} ", af[3]),
-                    format!("{:.2} ", af[4]),
-                    format!("{} ", af[5]),
-                    format!("{} ", af[6]),
-                    format!("{:.2} ", af[7]),
-                    format!("{} ", af[8]),
-                    format!("{} ", af[9]),
-                    format!("{:.2} ", af[10]),
-                    format!("{}", af[11]),
-                ];
+                let af = [&minmax.t_min, &minmax.t_selfish_min, &minmax.t_selfish_max, &minmax.t_max].iter()
+                    .flat_map(|paystats| [paystats.min().as_num::<f32>(), paystats.avg(), paystats.max().as_num::<f32>()])
+                    .take(n_columns)
+                    .collect::<Vec<_>>();
+                let astr = af.iter().enumerate()
+                    .map(|(i_f, f)| format!("{}{}",
+                        if i_f%3==1 {format!("{:.2}", f)} else {format!("{}", f)},
+                        if i_f+1<n_columns {" "} else {""},
+                    ))
+                    .collect::<Vec<_>>();
                 for (n_width, str) in an_width.iter_mut().zip(astr.iter()) {
                     *n_width = (*n_width).max(str.len());
                 }
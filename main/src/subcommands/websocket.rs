@@ -32,10 +32,13 @@ pub fn subcommand(str_subcommand: &str) -> clap::App {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
-enum VGamePhaseGeneric<DealCards, GamePreparations, DetermineRules, Game, GameResult> {
+enum VGamePhaseGeneric<DealCards, GamePreparations, DetermineRules, DerAlteMuss, Schieben, Hochzeit, Game, GameResult> {
     DealCards(DealCards),
     GamePreparations(GamePreparations),
     DetermineRules(DetermineRules),
+    DerAlteMuss(DerAlteMuss),
+    Schieben(Schieben),
+    Hochzeit(Hochzeit),
     Game(Game),
     GameResult(GameResult),
 }
@@ -64,6 +67,9 @@ type VGamePhase = VGamePhaseGeneric<
     SDealCards,
     SGamePreparations,
     SDetermineRules,
+    SDerAlteMuss,
+    SSchieben,
+    SHochzeit,
     SGame,
     SWebsocketGameResult,
 >;
@@ -71,6 +77,9 @@ type VGamePhaseActivePlayerInfo<'a> = VGamePhaseGeneric<
     (&'a SDealCards, <SDealCards as TGamePhase>::ActivePlayerInfo),
     (&'a SGamePreparations, <SGamePreparations as TGamePhase>::ActivePlayerInfo),
     (&'a SDetermineRules, <SDetermineRules as TGamePhase>::ActivePlayerInfo),
+    (&'a SDerAlteMuss, <SDerAlteMuss as TGamePhase>::ActivePlayerInfo),
+    (&'a SSchieben, <SSchieben as TGamePhase>::ActivePlayerInfo),
+    (&'a SHochzeit, <SHochzeit as TGamePhase>::ActivePlayerInfo),
     (&'a SGame, <SGame as TGamePhase>::ActivePlayerInfo),
     (&'a SWebsocketGameResult, <SWebsocketGameResult as TGamePhase>::ActivePlayerInfo),
 >;
@@ -101,12 +110,16 @@ fn rules_to_gamephaseaction<'retval, 'rules : 'retval, 'hand : 'retval>(slcruleg
 #[derive(Debug, Serialize, Deserialize, Clone)]
 enum VGameAction {
     Stoss,
+    AnnounceSchneiderSchwarz(/*n_eschneiderschwarz*/usize),
     Zugeben(SCard),
 }
 type VGamePhaseAction = VGamePhaseGeneric<
     /*DealCards announce_doubling*/ /*b_doubling*/bool,
     /*GamePreparations announce_game*/Option<SActivelyPlayableRulesIdentifier>,
     /*DetermineRules*/Option<SActivelyPlayableRulesIdentifier>,
+    /*DerAlteMuss*/Option<SActivelyPlayableRulesIdentifier>,
+    /*Schieben*/ /*b_schieben*/bool,
+    /*Hochzeit*/ /*ocard_exchange*/Option<SCard>,
     /*Game*/VGameAction,
     /*GameResult*/(),
 >;
@@ -127,6 +140,9 @@ impl VGamePhase {
             DealCards(dealcards) => internal(dealcards).map(DealCards),
             GamePreparations(gamepreparations) => internal(gamepreparations).map(GamePreparations),
             DetermineRules(determinerules) => internal(determinerules).map(DetermineRules),
+            DerAlteMuss(deraltemuss) => internal(deraltemuss).map(DerAlteMuss),
+            Schieben(schieben) => internal(schieben).map(Schieben),
+            Hochzeit(hochzeit) => internal(hochzeit).map(Hochzeit),
             Game(game) => internal(game).map(Game),
             GameResult(gameresult) => internal(gameresult).map(GameResult),
         }
@@ -225,6 +241,7 @@ impl SPlayers {
         oslcstich: Option<&[SStich]>,
         orules: Option<&dyn TRules>,
         f_cards: impl Fn(EPlayerIndex) -> Vec<SCard>,
+        otplepihand_open: Option<(EPlayerIndex, SHand)>,
         mut f_active: impl FnMut(EPlayerIndex, &mut Option<STimeoutCmd>)->VMessage,
         mut f_inactive: impl FnMut(&mut SPeer)->VMessage,
         oepi_timeout: Option<EPlayerIndex>,
@@ -236,6 +253,24 @@ impl SPlayers {
                 .map(|peer| peer.str_name.clone())
                 .unwrap_or_else(||"<BOT>".to_string())
         );
+        let sort_cards = |veccard: &mut Vec<SCard>| {
+            if let Some(rules) = orules {
+                rules.sort_cards_first_trumpf_then_farbe(veccard);
+            } else {
+                rulesramsch::SRulesRamsch::new( // TODO rules dummy is ugly
+                    /*n_price*/0, // irrelevant
+                    rulesramsch::VDurchmarsch::None, // irrelevant
+                    /*b_jungfrau*/false, // irrelevant
+                    /*on_price_stock*/None, // irrelevant
+                    /*b_schieben*/false, // irrelevant
+                ).sort_cards_first_trumpf_then_farbe(veccard);
+            }
+        };
+        let otplepiveccard_open = otplepihand_open.map(|(epi_open, hand_open)| {
+            let mut veccard_open = hand_open.cards().to_vec();
+            sort_cards(&mut veccard_open);
+            (epi_open, veccard_open)
+        });
         let communicate = |oepi: Option<EPlayerIndex>, veccard: Vec<SCard>, msg, peer: &mut SPeer| {
             let i_epi_relative = oepi.unwrap_or(EPlayerIndex::EPI0).to_usize();
             let playerindex_server_to_client = |epi: EPlayerIndex| {
@@ -266,6 +301,7 @@ impl SPlayers {
                 mapepistr: [String; EPlayerIndex::SIZE],
                 otplepistr_rules: Option<(EPlayerIndex, String)>,
                 oepi_timeout: Option<EPlayerIndex>,
+                otplepivecstr_open_hand: Option<(EPlayerIndex, Vec<String>)>,
             }
             let card_in_stich = |stich: &SStich, epi| {
                 stich.get(playerindex_client_to_server(epi)).map(SCard::to_string)
@@ -305,10 +341,16 @@ impl SPlayers {
                         )
                     ).into_raw(),
                     orules.map(|rules| (
-                        playerindex_server_to_client(rules.playerindex().unwrap_or(EPlayerIndex::EPI3)), // geber designates rules if no active
+                        playerindex_server_to_client(rules.playerindex().unwrap_or(EPlayerIndex::wrapped_from_usize(EPlayerIndex::SIZE-1))), // geber designates rules if no active
                         format!("{}", rules),
                     )),
                     oepi_timeout.map(playerindex_server_to_client),
+                    otplepiveccard_open.as_ref()
+                        .filter(|(epi_open, _veccard_open)| Some(*epi_open)!=oepi) // own hand is shown anyway
+                        .map(|(epi_open, veccard_open)| (
+                            playerindex_server_to_client(*epi_open),
+                            veccard_open.iter().map(SCard::to_string).collect(),
+                        )),
                 ))).into()
             ));
         };
@@ -317,14 +359,7 @@ impl SPlayers {
             let msg = f_active(epi, &mut activepeer.otimeoutcmd);
             if let Some(ref mut peer) = activepeer.opeer.as_mut() {
                 let mut veccard = f_cards(epi);
-                if let Some(rules) = orules {
-                    rules.sort_cards_first_trumpf_then_farbe(&mut veccard);
-                } else {
-                    rulesramsch::SRulesRamsch::new( // TODO rules dummy is ugly
-                        /*n_price*/0, // irrelevant
-                        rulesramsch::VDurchmarsch::None, // irrelevant
-                    ).sort_cards_first_trumpf_then_farbe(&mut veccard);
-                }
+                sort_cards(&mut veccard);
                 communicate(Some(epi), veccard, msg, peer);
             }
         }
@@ -386,9 +421,33 @@ impl STable {
                                     }
                                 }
                             },
+                            (VGamePhase::DerAlteMuss(ref mut deraltemuss), VGamePhaseAction::DerAlteMuss(ref orulesid)) => {
+                                if let Some((_epi_active, vecrulegroup)) = deraltemuss.which_player_can_do_something() {
+                                    if let Ok(Some(rules)) = find_rules_by_id(
+                                        &vecrulegroup,
+                                        deraltemuss.fullhand(epi),
+                                        orulesid
+                                    ) {
+                                        handle_err(deraltemuss.announce_game(epi, rules));
+                                    }
+                                }
+                            },
+                            (VGamePhase::Schieben(ref mut schieben), VGamePhaseAction::Schieben(b_schieben)) => {
+                                handle_err(schieben.announce_schieben(epi, b_schieben));
+                            },
+                            (VGamePhase::Hochzeit(ref mut hochzeit), VGamePhaseAction::Hochzeit(ocard_exchange)) => {
+                                handle_err(hochzeit.accept(epi, ocard_exchange));
+                            },
                             (VGamePhase::Game(ref mut game), VGamePhaseAction::Game(ref gameaction)) => {
                                 handle_err(match gameaction {
                                     VGameAction::Stoss => game.stoss(epi),
+                                    VGameAction::AnnounceSchneiderSchwarz(n_eschneiderschwarz) => {
+                                        if let Some(eschneiderschwarz) = ESchneiderSchwarz::checked_from_usize(*n_eschneiderschwarz) {
+                                            game.announce_schneider_schwarz(epi, eschneiderschwarz)
+                                        } else {
+                                            Err(format_err!("Invalid Schneider/Schwarz announcement"))
+                                        }
+                                    },
                                     VGameAction::Zugeben(card) => game.zugeben(*card, epi),
                                 });
                             },
@@ -415,12 +474,13 @@ impl STable {
                     // Players: E0 E1 E2 E3 [S0 S1 S2 ... SN] (S0 is longest waiting inactive player)
                     table.players.mapepiopeer.as_raw_mut().rotate_left(1);
                     // Players: E1 E2 E3 E0 [S0 S1 S2 ... SN]
-                    if let Some(peer_epi3) = table.players.mapepiopeer[EPlayerIndex::EPI3].opeer.take() {
-                        table.players.vecpeer.push(peer_epi3);
+                    let epi_last = EPlayerIndex::wrapped_from_usize(EPlayerIndex::SIZE-1);
+                    if let Some(peer_epi_last) = table.players.mapepiopeer[epi_last].opeer.take() {
+                        table.players.vecpeer.push(peer_epi_last);
                     }
                     // Players: E1 E2 E3 -- [S0 S1 S2 ... SN E0] (E1, E2, E3 may be None)
                     // Fill up players one after another
-                    assert!(table.players.mapepiopeer[EPlayerIndex::EPI3].opeer.is_none());
+                    assert!(table.players.mapepiopeer[epi_last].opeer.is_none());
                     for epi in EPlayerIndex::values() {
                         if table.players.mapepiopeer[epi].opeer.is_none() && !table.players.vecpeer.is_empty() {
                             table.players.mapepiopeer[epi].opeer = Some(table.players.vecpeer.remove(0));
@@ -439,12 +499,20 @@ impl STable {
                 ) -> R {
                     phase.finish().map_or_else(fn_err, fn_ok).into()
                 }
+                fn hochzeit_or_game(hochzeitorgame: VHochzeitOrGame) -> VGamePhase {
+                    match hochzeitorgame {
+                        VHochzeitOrGame::Hochzeit(hochzeit) => Hochzeit(hochzeit),
+                        VHochzeitOrGame::Game(game) => Game(game),
+                    }
+                }
                 if let Some(gamephase) = self.ogamephase.take() {
                     self.ogamephase = match gamephase {
                         DealCards(dealcards) => simple_transition(dealcards, GamePreparations, DealCards),
                         GamePreparations(gamepreparations) => match gamepreparations.finish() {
                             Ok(VGamePreparationsFinish::DetermineRules(determinerules)) => Some(DetermineRules(determinerules)),
                             Ok(VGamePreparationsFinish::DirectGame(game)) => Some(Game(game)),
+                            Ok(VGamePreparationsFinish::DerAlteMuss(deraltemuss)) => Some(DerAlteMuss(deraltemuss)),
+                            Ok(VGamePreparationsFinish::Schieben(schieben)) => Some(Schieben(schieben)),
                             Ok(VGamePreparationsFinish::Stock(gameresult)) => {
                                 let mapepiopeer = &mut self.players.mapepiopeer;
                                 gameresult.apply_payout(&mut self.n_stock, |epi, n_payout| {
@@ -456,7 +524,10 @@ impl STable {
                             },
                             Err(gamepreparations) => Some(GamePreparations(gamepreparations)),
                         }
-                        DetermineRules(determinerules) => simple_transition(determinerules, Game, DetermineRules),
+                        DetermineRules(determinerules) => simple_transition(determinerules, hochzeit_or_game, DetermineRules),
+                        DerAlteMuss(deraltemuss) => simple_transition(deraltemuss, hochzeit_or_game, DerAlteMuss),
+                        Schieben(schieben) => simple_transition(schieben, Game, Schieben),
+                        Hochzeit(hochzeit) => simple_transition(hochzeit, Game, Hochzeit),
                         Game(game) => simple_transition(
                             game,
                             |gameresult| GameResult(SWebsocketGameResult{
@@ -518,6 +589,7 @@ impl STable {
                                 /*oslcstich*/None,
                                 None,
                                 |epi| dealcards.first_hand_for(epi).into(),
+                                /*otplepihand_open*/None,
                                 |epi, otimeoutcmd| {
                                     if epi_doubling==epi {
                                         ask_with_timeout(
@@ -545,6 +617,7 @@ impl STable {
                                 /*oslcstich*/None,
                                 None,
                                 |epi| gamepreparations.fullhand(epi).get().to_vec(),
+                                /*otplepihand_open*/None,
                                 |epi, otimeoutcmd| {
                                     if epi_announce_game==epi {
                                         let itgamephaseaction_rules = rules_to_gamephaseaction(
@@ -611,6 +684,7 @@ impl STable {
                                 /*oslcstich*/None,
                                 None,
                                 |epi| determinerules.fullhand(epi).get().to_vec(),
+                                /*otplepihand_open*/None,
                                 |epi, otimeoutcmd| {
                                     if epi_determine==epi {
                                         let itgamephaseaction_rules = rules_to_gamephaseaction(
@@ -640,21 +714,117 @@ impl STable {
                                 Some(epi_determine),
                             );
                         },
+                        DerAlteMuss((deraltemuss, (epi_alter, vecrulegroup))) => {
+                            self.players.for_each(
+                                /*oslcstich*/None,
+                                None,
+                                |epi| deraltemuss.fullhand(epi).get().to_vec(),
+                                /*otplepihand_open*/None,
+                                |epi, otimeoutcmd| {
+                                    if epi_alter==epi {
+                                        let itgamephaseaction_rules = rules_to_gamephaseaction(
+                                            &vecrulegroup,
+                                            deraltemuss.fullhand(epi_alter),
+                                            VGamePhaseAction::DerAlteMuss,
+                                        );
+                                        let gamephaseaction_rules_default = unwrap!(itgamephaseaction_rules.clone().next()).1;
+                                        ask_with_timeout(
+                                            otimeoutcmd,
+                                            epi_alter,
+                                            "Niemand will spielen. Der Alte muss!".into(),
+                                            itgamephaseaction_rules,
+                                            self_mutex.clone(),
+                                            gamephaseaction_rules_default,
+                                        )
+                                    } else {
+                                        VMessage::Info(format!("Asking {:?} for forced game", epi_alter))
+                                    }
+                                },
+                                |_peer| VMessage::Info(format!("Asking {:?} for forced game", epi_alter)),
+                                Some(epi_alter),
+                            );
+                        },
+                        Schieben((schieben, epi_schieben)) => {
+                            self.players.for_each(
+                                /*oslcstich*/None,
+                                Some(schieben.rules.as_ref()),
+                                |epi| schieben.fullhand(epi).get().to_vec(),
+                                /*otplepihand_open*/None,
+                                |epi, otimeoutcmd| {
+                                    if epi_schieben==epi {
+                                        ask_with_timeout(
+                                            otimeoutcmd,
+                                            epi_schieben,
+                                            "Schieben?".into(),
+                                            [(true, "Schieben"), (false, "Nicht schieben")]
+                                                .into_iter()
+                                                .map(|(b_schieben, str_schieben)|
+                                                    (str_schieben.to_string(), VGamePhaseAction::Schieben(b_schieben))
+                                                ),
+                                            self_mutex.clone(),
+                                            VGamePhaseAction::Schieben(/*b_schieben*/false),
+                                        )
+                                    } else {
+                                        VMessage::Info(format!("Asking {:?} for schieben", epi_schieben))
+                                    }
+                                },
+                                |_peer| VMessage::Info(format!("Asking {:?} for schieben", epi_schieben)),
+                                Some(epi_schieben),
+                            );
+                        },
+                        Hochzeit((hochzeit, epi_hochzeit)) => {
+                            self.players.for_each(
+                                /*oslcstich*/None,
+                                Some(hochzeit.rules.as_ref()),
+                                |epi| hochzeit.fullhand(epi).get().to_vec(),
+                                /*otplepihand_open*/None,
+                                |epi, otimeoutcmd| {
+                                    if epi_hochzeit==epi {
+                                        ask_with_timeout(
+                                            otimeoutcmd,
+                                            epi_hochzeit,
+                                            format!("{}. Stelle bietet Hochzeit an. Annehmen?",
+                                                unwrap!(hochzeit.rules.playerindex_offering()).to_usize() + 1, // EPlayerIndex is 0-based
+                                            ),
+                                            std::iter::once(("Nicht annehmen".to_string(), VGamePhaseAction::Hochzeit(None)))
+                                                .chain(hochzeit.cards_to_exchange(epi_hochzeit).map(|card|
+                                                    (format!("Annehmen, {} abgeben", card), VGamePhaseAction::Hochzeit(Some(card)))
+                                                )),
+                                            self_mutex.clone(),
+                                            VGamePhaseAction::Hochzeit(/*ocard_exchange*/None),
+                                        )
+                                    } else {
+                                        VMessage::Info(format!("Asking {:?} for Hochzeit", epi_hochzeit))
+                                    }
+                                },
+                                |_peer| VMessage::Info(format!("Asking {:?} for Hochzeit", epi_hochzeit)),
+                                Some(epi_hochzeit),
+                            );
+                        },
                         Game((game, (epi_card, vecepi_stoss))) => {
                             self.players.for_each(
                                 Some(game.stichseq.visible_stichs()),
                                 Some(game.rules.as_ref()),
                                 |epi| game.ahand[epi].cards().to_vec(),
+                                game.open_hand(),
                                 |epi, otimeoutcmd| {
-                                    let ostrgamephaseaction = if_then_some!(vecepi_stoss.contains(&epi),
-                                        ("Stoss".into(), VGamePhaseAction::Game(VGameAction::Stoss))
-                                    );
+                                    let vecstrgamephaseaction = if_then_some!(vecepi_stoss.contains(&epi),
+                                        (
+                                            EStoss::next(&game.vecstoss).map_or("Stoss".into(), |estoss| format!("{:?}", estoss)),
+                                            VGamePhaseAction::Game(VGameAction::Stoss),
+                                        )
+                                    ).into_iter()
+                                        .chain(game.schneider_schwarz_announceable(epi).map(|eschneiderschwarz| (
+                                            format!("{:?} ansagen", eschneiderschwarz),
+                                            VGamePhaseAction::Game(VGameAction::AnnounceSchneiderSchwarz(eschneiderschwarz.to_usize())),
+                                        )))
+                                        .collect::<Vec<_>>();
                                     if epi_card==epi {
                                         ask_with_timeout(
                                             otimeoutcmd,
                                             epi_card,
                                             "".into(),
-                                            ostrgamephaseaction.into_iter(),
+                                            vecstrgamephaseaction.into_iter(),
                                             self_mutex.clone(),
                                             VGamePhaseAction::Game(VGameAction::Zugeben(
                                                 *unwrap!(game.rules.all_allowed_cards(
@@ -663,10 +833,10 @@ impl STable {
                                                 ).choose(&mut rand::thread_rng()))
                                             )),
                                         )
-                                    } else if let Some(strgamephaseaction) = ostrgamephaseaction {
+                                    } else if !vecstrgamephaseaction.is_empty() {
                                         VMessage::Ask{
                                             str_question: "".into(),
-                                            vecstrgamephaseaction: vec![strgamephaseaction],
+                                            vecstrgamephaseaction,
                                         }
                                     } else {
                                         VMessage::Info(format!("Asking {:?} for card", epi_card))
@@ -685,6 +855,7 @@ impl STable {
                                     game.rules.as_ref()
                                 ),
                                 |_epi| Vec::new(),
+                                /*otplepihand_open*/None,
                                 |epi, otimeoutcmd| {
                                     if !mapepib_confirmed[epi] {
                                         ask_with_timeout(
@@ -715,6 +886,7 @@ impl STable {
                 /*oslcstich*/None,
                 None,
                 |_epi| Vec::new(),
+                /*otplepihand_open*/None,
                 |_oepi, _otimeoutcmd| VMessage::Info("Waiting for more players.".into()),
                 |_peer| VMessage::Info("Waiting for more players.".into()),
                 None,
@@ -855,6 +1027,9 @@ async fn internal_run(ruleset: SRuleSet) -> Result<(), Error> {
 }
 
 pub fn run(clapmatches: &clap::ArgMatches) -> Result<(), Error> {
+    if cfg!(feature="dreier") {
+        bail!("websocket client (tools/site) supports only four players");
+    }
     task::block_on(internal_run(super::get_ruleset(clapmatches)?))
 }
 
@@ -76,14 +76,14 @@ macro_rules! cartesian_match(
     (
         $macro_callback: ident,
         $(match ($e: expr) {
-            $($x: pat => $y: tt,)*
+            $($(#[$attr: meta])* $x: pat => $y: tt,)*
         },)*
     ) => {
         cartesian_match!(@p0,
             $macro_callback,
             (),
             $(match ($e) {
-                $($x => $y,)*
+                $($(#[$attr])* $x => $y,)*
             },)*
         )
     };
@@ -91,22 +91,22 @@ macro_rules! cartesian_match(
         $macro_callback: ident,
         $rest_packed: tt,
         match ($e: expr) {
-            $($x: pat => $y: tt,)*
+            $($(#[$attr: meta])* $x: pat => $y: tt,)*
         },
         $(match ($e2: expr) {
-            $($x2: pat => $y2: tt,)*
+            $($(#[$attr2: meta])* $x2: pat => $y2: tt,)*
         },)*
     ) => {
         cartesian_match!(@p0,
             $macro_callback,
             (
                 match ($e) {
-                    $($x => $y,)*
+                    $($(#[$attr])* $x => $y,)*
                 },
                 $rest_packed,
             ),
             $(match ($e2) {
-                $($x2 => $y2,)*
+                $($(#[$attr2])* $x2 => $y2,)*
             },)*
         )
     };
@@ -125,13 +125,13 @@ macro_rules! cartesian_match(
         @matched{$matched_packed: tt},
         (
             match ($e: expr) {
-                $($x: pat => $y: tt,)*
+                $($(#[$attr: meta])* $x: pat => $y: tt,)*
             },
             $rest_packed: tt,
         ),
     ) => {
         match $e {
-            $($x => cartesian_match!(@p1,
+            $($(#[$attr])* $x => cartesian_match!(@p1,
                 $macro_callback,
                 @matched{ ($matched_packed, $y,) },
                 $rest_packed,
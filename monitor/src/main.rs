@@ -149,6 +149,8 @@ fn main() -> Result<(), failure::Error> {
                             str_hand,
                             "--cards-on-table",
                             str_cards_as_played,
+                            "--max-duration",
+                            "2",
                         ])
                         .stdout(std::process::Stdio::piped())
                         .spawn()